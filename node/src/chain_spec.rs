@@ -1,16 +1,35 @@
 use node_template_runtime::{
-	AccountId, AuraConfig, BalancesConfig, GrandpaConfig, RuntimeGenesisConfig, Signature,
-	SudoConfig, SystemConfig, WASM_BINARY,
+	AccountId, AssetId, AssetsConfig, AuraConfig, Balance, BalancesConfig, DexAssetId, DexConfig,
+	GrandpaConfig, MinLpAssetId, RuntimeGenesisConfig, Signature, SudoConfig, SystemConfig,
+	WASM_BINARY,
 };
 use sc_service::ChainType;
 use sp_consensus_aura::sr25519::AuthorityId as AuraId;
 use sp_consensus_grandpa::AuthorityId as GrandpaId;
-use sp_core::{sr25519, Pair, Public};
+use sp_core::{sr25519, Get, Pair, Public};
 use sp_runtime::traits::{IdentifyAccount, Verify};
 
 // The URL for the telemetry server.
 // const STAGING_TELEMETRY_URL: &str = "wss://telemetry.polkadot.io/submit/";
 
+/// The demo meme assets of the test networks, given as `(id, name, symbol)`.
+const DEMO_ASSETS: [(AssetId, &str, &str); 3] =
+	[(1, "Dogecoin", "DOGE"), (2, "Pepe", "PEPE"), (3, "Shiba Inu", "SHIB")];
+
+/// The decimals of the demo assets, the same as those of the native currency.
+const DEMO_ASSET_DECIMALS: u8 = 10;
+
+/// The native currency given to each pre-funded account.
+const ENDOWMENT: Balance = 1 << 60;
+
+/// Each of the demo assets given to each pre-funded account.
+const DEMO_ASSET_ENDOWMENT: Balance = 1 << 59;
+
+/// The highest total issuance of the native currency or of a demo asset at genesis, low enough
+/// for the product of the reserves of any pool to fit in `Balance`. The swap quotes don't need
+/// more headroom, as they multiply the input by the fee and the reserve in 256 bits.
+const MAX_TOTAL_ISSUANCE: Balance = u64::MAX as Balance;
+
 /// Specialized `ChainSpec`. This is a specialization of the general Substrate ChainSpec type.
 pub type ChainSpec = sc_service::GenericChainSpec<RuntimeGenesisConfig>;
 
@@ -132,6 +151,9 @@ fn testnet_genesis(
 	endowed_accounts: Vec<AccountId>,
 	_enable_println: bool,
 ) -> RuntimeGenesisConfig {
+	assert_sane_issuance(ENDOWMENT, endowed_accounts.len());
+	assert_sane_issuance(DEMO_ASSET_ENDOWMENT, endowed_accounts.len());
+
 	RuntimeGenesisConfig {
 		system: SystemConfig {
 			// Add Wasm runtime to storage.
@@ -139,8 +161,8 @@ fn testnet_genesis(
 			..Default::default()
 		},
 		balances: BalancesConfig {
-			// Configure endowed accounts with initial balance of 1 << 60.
-			balances: endowed_accounts.iter().cloned().map(|k| (k, 1 << 60)).collect(),
+			// Configure endowed accounts with initial balance of `ENDOWMENT`.
+			balances: endowed_accounts.iter().cloned().map(|k| (k, ENDOWMENT)).collect(),
 		},
 		aura: AuraConfig {
 			authorities: initial_authorities.iter().map(|x| (x.0.clone())).collect(),
@@ -151,10 +173,48 @@ fn testnet_genesis(
 		},
 		sudo: SudoConfig {
 			// Assign network admin rights.
-			key: Some(root_key),
+			key: Some(root_key.clone()),
 		},
 		transaction_payment: Default::default(),
-		assets: Default::default(),
-		dex: Default::default(),
+		assets: AssetsConfig {
+			// The sudo account owns the demo assets and every endowed account gets some of each
+			assets: DEMO_ASSETS.iter().map(|(id, _, _)| (*id, root_key.clone(), true, 1)).collect(),
+			metadata: DEMO_ASSETS
+				.iter()
+				.map(|(id, name, symbol)| {
+					(*id, name.as_bytes().to_vec(), symbol.as_bytes().to_vec(), DEMO_ASSET_DECIMALS)
+				})
+				.collect(),
+			accounts: DEMO_ASSETS
+				.iter()
+				.flat_map(|(id, _, _)| {
+					endowed_accounts.iter().map(|k| (*id, k.clone(), DEMO_ASSET_ENDOWMENT))
+				})
+				.collect(),
+		},
+		dex: DexConfig { pools: demo_pools(root_key) },
 	}
 }
+
+/// The demo pools of the test networks, whose liquidity is provided by `provider`.
+fn demo_pools(
+	provider: AccountId,
+) -> Vec<(AssetId, DexAssetId, DexAssetId, Balance, Balance, AccountId)> {
+	let (doge, pepe, shib) = (DexAssetId::Asset(1), DexAssetId::Asset(2), DexAssetId::Asset(3));
+	let lp_asset_id = MinLpAssetId::get();
+	vec![
+		(lp_asset_id, DexAssetId::Native, doge, 1 << 50, 1 << 57, provider.clone()),
+		(lp_asset_id + 1, DexAssetId::Native, pepe, 1 << 50, 1 << 58, provider.clone()),
+		(lp_asset_id + 2, doge, shib, 1 << 56, 1 << 58, provider),
+	]
+}
+
+/// Ensures that giving `endowment` to each of `accounts` accounts keeps the total issuance below
+/// `MAX_TOTAL_ISSUANCE`.
+fn assert_sane_issuance(endowment: Balance, accounts: usize) {
+	let total_issuance = endowment.checked_mul(accounts as Balance);
+	assert!(
+		matches!(total_issuance, Some(issuance) if issuance <= MAX_TOTAL_ISSUANCE),
+		"The total issuance of the test network is too high"
+	);
+}
@@ -0,0 +1,172 @@
+//! RPC interface for the dex pallet.
+
+use std::sync::Arc;
+
+use codec::Codec;
+use jsonrpsee::{
+	core::RpcResult,
+	proc_macros::rpc,
+	types::error::{CallError, ErrorObject},
+};
+pub use pallet_dex_runtime_api::{DexApi as DexRuntimeApi, PoolInfo};
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_runtime::traits::Block as BlockT;
+
+#[rpc(client, server)]
+pub trait DexApi<BlockHash, AssetId, LpAssetId, Balance> {
+	/// Returns the amount of `asset_out` received for exactly `amount_in` of `asset_in`.
+	#[method(name = "dex_quoteExactIn")]
+	fn quote_exact_in(
+		&self,
+		asset_in: AssetId,
+		asset_out: AssetId,
+		amount_in: Balance,
+		at: Option<BlockHash>,
+	) -> RpcResult<Option<Balance>>;
+
+	/// Returns the amount of `asset_in` needed to receive exactly `amount_out` of `asset_out`.
+	#[method(name = "dex_quoteExactOut")]
+	fn quote_exact_out(
+		&self,
+		asset_in: AssetId,
+		asset_out: AssetId,
+		amount_out: Balance,
+		at: Option<BlockHash>,
+	) -> RpcResult<Option<Balance>>;
+
+	/// Returns the reserves of the pool of `asset_a` and `asset_b`, in the order the assets were
+	/// given.
+	#[method(name = "dex_getReserves")]
+	fn get_reserves(
+		&self,
+		asset_a: AssetId,
+		asset_b: AssetId,
+		at: Option<BlockHash>,
+	) -> RpcResult<Option<(Balance, Balance)>>;
+
+	/// Returns the pool of `asset_a` and `asset_b`.
+	#[method(name = "dex_getPool")]
+	fn get_pool(
+		&self,
+		asset_a: AssetId,
+		asset_b: AssetId,
+		at: Option<BlockHash>,
+	) -> RpcResult<Option<PoolInfo<AssetId, LpAssetId, Balance>>>;
+
+	/// Returns all liquidity pools.
+	#[method(name = "dex_listPools")]
+	fn list_pools(
+		&self,
+		at: Option<BlockHash>,
+	) -> RpcResult<Vec<PoolInfo<AssetId, LpAssetId, Balance>>>;
+}
+
+/// Provides RPC methods to query the dex pallet.
+pub struct Dex<C, P> {
+	client: Arc<C>,
+	_marker: std::marker::PhantomData<P>,
+}
+
+impl<C, P> Dex<C, P> {
+	/// Creates a new instance of the Dex RPC helper.
+	pub fn new(client: Arc<C>) -> Self {
+		Self { client, _marker: Default::default() }
+	}
+}
+
+/// Error type of this RPC api.
+pub enum Error {
+	/// The call to runtime failed.
+	RuntimeError,
+}
+
+impl From<Error> for i32 {
+	fn from(e: Error) -> i32 {
+		match e {
+			Error::RuntimeError => 1,
+		}
+	}
+}
+
+fn runtime_error(e: sp_api::ApiError) -> jsonrpsee::core::Error {
+	CallError::Custom(ErrorObject::owned(
+		Error::RuntimeError.into(),
+		"Unable to query the dex.",
+		Some(e.to_string()),
+	))
+	.into()
+}
+
+impl<C, Block, AssetId, LpAssetId, Balance>
+	DexApiServer<<Block as BlockT>::Hash, AssetId, LpAssetId, Balance> for Dex<C, Block>
+where
+	Block: BlockT,
+	C: ProvideRuntimeApi<Block> + HeaderBackend<Block> + Send + Sync + 'static,
+	C::Api: DexRuntimeApi<Block, AssetId, LpAssetId, Balance>,
+	AssetId: Codec + Send + Sync + 'static,
+	LpAssetId: Codec + Send + Sync + 'static,
+	Balance: Codec + Send + Sync + 'static,
+{
+	fn quote_exact_in(
+		&self,
+		asset_in: AssetId,
+		asset_out: AssetId,
+		amount_in: Balance,
+		at: Option<Block::Hash>,
+	) -> RpcResult<Option<Balance>> {
+		let api = self.client.runtime_api();
+		let at_hash = at.unwrap_or_else(|| self.client.info().best_hash);
+
+		api.quote_exact_in(at_hash, asset_in, asset_out, amount_in)
+			.map_err(runtime_error)
+	}
+
+	fn quote_exact_out(
+		&self,
+		asset_in: AssetId,
+		asset_out: AssetId,
+		amount_out: Balance,
+		at: Option<Block::Hash>,
+	) -> RpcResult<Option<Balance>> {
+		let api = self.client.runtime_api();
+		let at_hash = at.unwrap_or_else(|| self.client.info().best_hash);
+
+		api.quote_exact_out(at_hash, asset_in, asset_out, amount_out)
+			.map_err(runtime_error)
+	}
+
+	fn get_reserves(
+		&self,
+		asset_a: AssetId,
+		asset_b: AssetId,
+		at: Option<Block::Hash>,
+	) -> RpcResult<Option<(Balance, Balance)>> {
+		let api = self.client.runtime_api();
+		let at_hash = at.unwrap_or_else(|| self.client.info().best_hash);
+
+		api.get_reserves(at_hash, asset_a, asset_b).map_err(runtime_error)
+	}
+
+	fn get_pool(
+		&self,
+		asset_a: AssetId,
+		asset_b: AssetId,
+		at: Option<Block::Hash>,
+	) -> RpcResult<Option<PoolInfo<AssetId, LpAssetId, Balance>>> {
+		let api = self.client.runtime_api();
+		let at_hash = at.unwrap_or_else(|| self.client.info().best_hash);
+
+		api.get_pool(at_hash, asset_a, asset_b).map_err(runtime_error)
+	}
+
+	fn list_pools(
+		&self,
+		at: Option<Block::Hash>,
+	) -> RpcResult<Vec<PoolInfo<AssetId, LpAssetId, Balance>>> {
+		let api = self.client.runtime_api();
+		let at_hash = at.unwrap_or_else(|| self.client.info().best_hash);
+
+		api.list_pools(at_hash).map_err(runtime_error)
+	}
+}
@@ -0,0 +1,58 @@
+//! Runtime API definition for the dex pallet.
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use codec::{Codec, Decode, Encode};
+use scale_info::TypeInfo;
+#[cfg(feature = "std")]
+use serde::{Deserialize, Serialize};
+use sp_runtime::{Permill, RuntimeDebug};
+use sp_std::vec::Vec;
+
+/// A liquidity pool as exposed to clients, with its assets in pool order.
+#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub struct PoolInfo<AssetId, LpAssetId, Balance> {
+	/// Asset id of the LP token of the pool in `pallet-assets`.
+	pub lp_asset_id: LpAssetId,
+	pub asset_a: AssetId,
+	pub asset_b: AssetId,
+	pub reserve_a: Balance,
+	pub reserve_b: Balance,
+	/// Swap fee taken from the input amount.
+	pub fee: Permill,
+}
+
+sp_api::decl_runtime_apis! {
+	pub trait DexApi<AssetId, LpAssetId, Balance>
+	where
+		AssetId: Codec,
+		LpAssetId: Codec,
+		Balance: Codec,
+	{
+		/// Returns the amount of `asset_out` received for exactly `amount_in` of `asset_in`.
+		fn quote_exact_in(asset_in: AssetId, asset_out: AssetId, amount_in: Balance) -> Option<Balance>;
+
+		/// Returns the amount of `asset_in` needed to receive exactly `amount_out` of `asset_out`.
+		fn quote_exact_out(asset_in: AssetId, asset_out: AssetId, amount_out: Balance) -> Option<Balance>;
+
+		/// Returns the reserves of the pool of `asset_a` and `asset_b`, in the order the assets
+		/// were given.
+		fn get_reserves(asset_a: AssetId, asset_b: AssetId) -> Option<(Balance, Balance)>;
+
+		/// Returns the pool of `asset_a` and `asset_b`.
+		fn get_pool(asset_a: AssetId, asset_b: AssetId) -> Option<PoolInfo<AssetId, LpAssetId, Balance>>;
+
+		/// Returns all liquidity pools.
+		fn list_pools() -> Vec<PoolInfo<AssetId, LpAssetId, Balance>>;
+
+		/// Returns the path of at most `max_hops` pools from `asset_in` to `asset_out` giving the
+		/// highest output for `amount_in`, along with that output amount.
+		fn find_best_route(
+			asset_in: AssetId,
+			amount_in: Balance,
+			asset_out: AssetId,
+			max_hops: u32,
+		) -> Option<(Vec<AssetId>, Balance)>;
+	}
+}
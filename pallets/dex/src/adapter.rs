@@ -0,0 +1,156 @@
+use crate::*;
+use frame_support::traits::tokens::{
+	DepositConsequence, Fortitude, Preservation, Provenance, WithdrawConsequence,
+};
+use frame_support::traits::EnsureOriginWithArg;
+use sp_runtime::DispatchError;
+use sp_std::marker::PhantomData;
+
+/// Fungibles adapter that routes [`NativeOrAssetId::Native`] to `NativeBalance` and every
+/// [`NativeOrAssetId::Asset`] to `Fungibles`, so pools can hold the native currency like any
+/// other asset.
+pub struct NativeOrAssetAdapter<T>(PhantomData<T>);
+
+impl<T: Config> fungibles::Inspect<AccountIdOf<T>> for NativeOrAssetAdapter<T> {
+	type AssetId = NativeOrAssetIdOf<T>;
+	type Balance = AssetBalanceOf<T>;
+
+	fn total_issuance(asset: Self::AssetId) -> Self::Balance {
+		match asset {
+			NativeOrAssetId::Native => <T::NativeBalance as fungible::Inspect<_>>::total_issuance(),
+			NativeOrAssetId::Asset(id) =>
+				<T::Fungibles as fungibles::Inspect<_>>::total_issuance(id),
+		}
+	}
+
+	fn minimum_balance(asset: Self::AssetId) -> Self::Balance {
+		match asset {
+			NativeOrAssetId::Native =>
+				<T::NativeBalance as fungible::Inspect<_>>::minimum_balance(),
+			NativeOrAssetId::Asset(id) =>
+				<T::Fungibles as fungibles::Inspect<_>>::minimum_balance(id),
+		}
+	}
+
+	fn total_balance(asset: Self::AssetId, who: &AccountIdOf<T>) -> Self::Balance {
+		match asset {
+			NativeOrAssetId::Native =>
+				<T::NativeBalance as fungible::Inspect<_>>::total_balance(who),
+			NativeOrAssetId::Asset(id) =>
+				<T::Fungibles as fungibles::Inspect<_>>::total_balance(id, who),
+		}
+	}
+
+	fn balance(asset: Self::AssetId, who: &AccountIdOf<T>) -> Self::Balance {
+		match asset {
+			NativeOrAssetId::Native => <T::NativeBalance as fungible::Inspect<_>>::balance(who),
+			NativeOrAssetId::Asset(id) => <T::Fungibles as fungibles::Inspect<_>>::balance(id, who),
+		}
+	}
+
+	fn reducible_balance(
+		asset: Self::AssetId,
+		who: &AccountIdOf<T>,
+		preservation: Preservation,
+		force: Fortitude,
+	) -> Self::Balance {
+		match asset {
+			NativeOrAssetId::Native =>
+				<T::NativeBalance as fungible::Inspect<_>>::reducible_balance(
+					who,
+					preservation,
+					force,
+				),
+			NativeOrAssetId::Asset(id) =>
+				<T::Fungibles as fungibles::Inspect<_>>::reducible_balance(
+					id,
+					who,
+					preservation,
+					force,
+				),
+		}
+	}
+
+	fn can_deposit(
+		asset: Self::AssetId,
+		who: &AccountIdOf<T>,
+		amount: Self::Balance,
+		provenance: Provenance,
+	) -> DepositConsequence {
+		match asset {
+			NativeOrAssetId::Native =>
+				<T::NativeBalance as fungible::Inspect<_>>::can_deposit(who, amount, provenance),
+			NativeOrAssetId::Asset(id) =>
+				<T::Fungibles as fungibles::Inspect<_>>::can_deposit(id, who, amount, provenance),
+		}
+	}
+
+	fn can_withdraw(
+		asset: Self::AssetId,
+		who: &AccountIdOf<T>,
+		amount: Self::Balance,
+	) -> WithdrawConsequence<Self::Balance> {
+		match asset {
+			NativeOrAssetId::Native =>
+				<T::NativeBalance as fungible::Inspect<_>>::can_withdraw(who, amount),
+			NativeOrAssetId::Asset(id) =>
+				<T::Fungibles as fungibles::Inspect<_>>::can_withdraw(id, who, amount),
+		}
+	}
+
+	fn asset_exists(asset: Self::AssetId) -> bool {
+		match asset {
+			NativeOrAssetId::Native => true,
+			NativeOrAssetId::Asset(id) => <T::Fungibles as fungibles::Inspect<_>>::asset_exists(id),
+		}
+	}
+}
+
+impl<T: Config> NativeOrAssetAdapter<T> {
+	/// Transfers `amount` of `asset` from `source` to `dest`.
+	pub fn transfer(
+		asset: NativeOrAssetIdOf<T>,
+		source: &AccountIdOf<T>,
+		dest: &AccountIdOf<T>,
+		amount: AssetBalanceOf<T>,
+		preservation: Preservation,
+	) -> Result<AssetBalanceOf<T>, DispatchError> {
+		match asset {
+			NativeOrAssetId::Native => <T::NativeBalance as fungible::Mutate<_>>::transfer(
+				source,
+				dest,
+				amount,
+				preservation,
+			),
+			NativeOrAssetId::Asset(id) => <T::Fungibles as fungibles::Mutate<_>>::transfer(
+				id,
+				source,
+				dest,
+				amount,
+				preservation,
+			),
+		}
+	}
+}
+
+/// `CreateOrigin` for `pallet_assets` that only lets `Inner` create assets outside of the range
+/// reserved for LP tokens, so that nobody can take the ids the pallet gives to new pools.
+pub struct EnsureNotLpAssetId<T, Inner>(PhantomData<(T, Inner)>);
+
+impl<T: Config, O, Inner: EnsureOriginWithArg<O, AssetIdOf<T>>> EnsureOriginWithArg<O, AssetIdOf<T>>
+	for EnsureNotLpAssetId<T, Inner>
+{
+	type Success = Inner::Success;
+
+	fn try_origin(o: O, asset_id: &AssetIdOf<T>) -> Result<Self::Success, O> {
+		if (T::MinLpAssetId::get()..=T::MaxLpAssetId::get()).contains(asset_id) {
+			return Err(o)
+		}
+		Inner::try_origin(o, asset_id)
+	}
+
+	#[cfg(feature = "runtime-benchmarks")]
+	fn try_successful_origin(asset_id: &AssetIdOf<T>) -> Result<O, ()> {
+		Inner::try_successful_origin(asset_id)
+	}
+}
@@ -5,7 +5,67 @@ use super::*;
 #[allow(unused)]
 use crate::Pallet as Dex;
 use frame_benchmarking::v2::*;
+use frame_support::assert_ok;
+use frame_support::traits::tokens::Preservation;
 use frame_system::RawOrigin;
+use sp_runtime::traits::Saturating;
+use sp_std::vec::Vec;
+
+fn create_asset<T: Config>(asset: AssetIdOf<T>, owner: &T::AccountId) {
+	let _ = T::Fungibles::create(asset.clone(), owner.clone(), true, AssetBalanceOf::<T>::one());
+	let _ = T::Fungibles::mint_into(asset.clone(), owner, 1_000_000_000u32.into());
+}
+
+/// Gives `who` enough of the native currency to pay the pool creation deposit and the existential
+/// deposit of the pool account, and to stay alive.
+fn fund_pool_deposit<T: Config>(who: &T::AccountId) {
+	let existential_deposit = <T::NativeBalance as fungible::Inspect<_>>::minimum_balance();
+	let amount = T::PoolDeposit::get()
+		.saturating_add(existential_deposit)
+		.saturating_add(existential_deposit);
+	let _ = <T::NativeBalance as fungible::Mutate<_>>::set_balance(who, amount);
+}
+
+fn create_pool<T: Config>(caller: &T::AccountId, asset_a: AssetIdOf<T>, asset_b: AssetIdOf<T>) {
+	fund_pool_deposit::<T>(caller);
+	assert_ok!(Dex::<T>::create_pool(
+		RawOrigin::Signed(caller.clone()).into(),
+		asset_a.into(),
+		asset_b.into(),
+		T::SwapFeeTiers::get()[0],
+	));
+	assert_ok!(Dex::<T>::mint(
+		RawOrigin::Signed(caller.clone()).into(),
+		asset_a.into(),
+		asset_b.into(),
+		100_000u32.into(),
+		200_000u32.into(),
+		0u32.into(),
+		0u32.into(),
+		None
+	));
+}
+
+/// Switches the protocol fee on and trades in the pool, so that the next liquidity event has
+/// to mint the protocol fee.
+fn accrue_protocol_fee<T: Config>(
+	caller: &T::AccountId,
+	asset_a: AssetIdOf<T>,
+	asset_b: AssetIdOf<T>,
+) {
+	assert_ok!(Dex::<T>::set_protocol_fee(
+		RawOrigin::Root.into(),
+		Some((caller.clone(), Perbill::from_percent(20)))
+	));
+	create_pool::<T>(caller, asset_a, asset_b);
+	assert_ok!(Dex::<T>::swap(
+		RawOrigin::Signed(caller.clone()).into(),
+		asset_a.into(),
+		asset_b.into(),
+		10_000u32.into(),
+		None,
+	));
+}
 
 #[benchmarks]
 mod benchmarks {
@@ -13,38 +73,365 @@ mod benchmarks {
 	use crate::types::AssetPair;
 
 	#[benchmark]
-	fn mint() {
+	fn create_pool() {
 		let asset_a: AssetIdOf<T> = 1u32;
 		let asset_b: AssetIdOf<T> = 2u32;
-		let pool_id: AssetIdOf<T> = 100u32;
-		let amount_a: AssetBalanceOf<T> = 100000u32.into();
-		let amount_b: AssetBalanceOf<T> = 200000u32.into();
+		let fee_tier = T::SwapFeeTiers::get()[0];
 		let caller: T::AccountId = whitelisted_caller();
-		let _ =
-			T::Fungibles::create(asset_a.clone(), caller.clone(), true, AssetBalanceOf::<T>::one());
-		let _ = T::Fungibles::mint_into(asset_a.clone(), &caller, 1_000_000_000u32.into());
-		let _ =
-			T::Fungibles::create(asset_b.clone(), caller.clone(), true, AssetBalanceOf::<T>::one());
-		let _ = T::Fungibles::mint_into(asset_b.clone(), &caller, 1_000_000_000u32.into());
+		create_asset::<T>(asset_a, &caller);
+		create_asset::<T>(asset_b, &caller);
+		fund_pool_deposit::<T>(&caller);
 
 		#[extrinsic_call]
-		_(RawOrigin::Signed(caller.clone()), pool_id, asset_a, asset_b, amount_a, amount_b);
+		_(RawOrigin::Signed(caller.clone()), asset_a.into(), asset_b.into(), fee_tier);
 
-		let pool_key = AssetPair::new(asset_a.clone(), asset_b.clone());
+		let pool_key = AssetPair::new(asset_a.into(), asset_b.into());
 		// Panics if pool does not exist
 		let pool = LiquidityPools::<T>::get(pool_key).unwrap();
+		assert_eq!(pool.depositor, caller);
+		assert_eq!(
+			<T::NativeBalance as fungible::hold::Inspect<_>>::balance_on_hold(
+				&HoldReason::PoolDeposit.into(),
+				&caller
+			),
+			T::PoolDeposit::get()
+		);
+	}
 
-		let pool_asset_a_balance = T::Fungibles::balance(asset_a.clone(), &pool.manager);
-		assert_eq!(pool_asset_a_balance, 100_000u32.into());
+	#[benchmark]
+	fn mint() {
+		let asset_a: AssetIdOf<T> = 1u32;
+		let asset_b: AssetIdOf<T> = 2u32;
+		let caller: T::AccountId = whitelisted_caller();
+		create_asset::<T>(asset_a, &caller);
+		create_asset::<T>(asset_b, &caller);
+		accrue_protocol_fee::<T>(&caller, asset_a, asset_b);
+		let pool_key = AssetPair::new(asset_a.into(), asset_b.into());
+		let pool = LiquidityPools::<T>::get(pool_key.clone()).unwrap();
+		let lp_balance = T::Fungibles::balance(pool.id, &caller);
+
+		#[extrinsic_call]
+		mint(
+			RawOrigin::Signed(caller.clone()),
+			asset_a.into(),
+			asset_b.into(),
+			100_000u32.into(),
+			200_000u32.into(),
+			0u32.into(),
+			0u32.into(),
+			None,
+		);
 
-		let pool_asset_b_balance = T::Fungibles::balance(asset_b.clone(), &pool.manager);
-		assert_eq!(pool_asset_b_balance, 200_000u32.into());
+		assert!(T::Fungibles::balance(pool.id, &caller) > lp_balance);
+	}
 
-		let caller_asset_a_balance = T::Fungibles::balance(asset_a.clone(), &caller);
-		assert_eq!(caller_asset_a_balance, 999_900_000u32.into());
+	#[benchmark]
+	fn burn_partial() {
+		let asset_a: AssetIdOf<T> = 1u32;
+		let asset_b: AssetIdOf<T> = 2u32;
+		let caller: T::AccountId = whitelisted_caller();
+		create_asset::<T>(asset_a, &caller);
+		create_asset::<T>(asset_b, &caller);
+		accrue_protocol_fee::<T>(&caller, asset_a, asset_b);
+		let pool_key = AssetPair::new(asset_a.into(), asset_b.into());
+		let pool = LiquidityPools::<T>::get(pool_key.clone()).unwrap();
+		let lp_balance = T::Fungibles::balance(pool.id, &caller);
+
+		#[extrinsic_call]
+		burn(
+			RawOrigin::Signed(caller.clone()),
+			asset_a.into(),
+			asset_b.into(),
+			10_000u32.into(),
+			0u32.into(),
+			0u32.into(),
+			None,
+		);
+
+		assert!(LiquidityPools::<T>::contains_key(pool_key));
+		assert!(T::Fungibles::balance(pool.id, &caller) < lp_balance);
+	}
+
+	#[benchmark]
+	fn burn_full() {
+		let asset_a: AssetIdOf<T> = 1u32;
+		let asset_b: AssetIdOf<T> = 2u32;
+		let caller: T::AccountId = whitelisted_caller();
+		create_asset::<T>(asset_a, &caller);
+		create_asset::<T>(asset_b, &caller);
+		create_pool::<T>(&caller, asset_a, asset_b);
+		let pool =
+			LiquidityPools::<T>::get(AssetPair::new(asset_a.into(), asset_b.into())).unwrap();
+		let lp_balance = T::Fungibles::balance(pool.id, &caller);
+
+		#[extrinsic_call]
+		burn(
+			RawOrigin::Signed(caller.clone()),
+			asset_a.into(),
+			asset_b.into(),
+			lp_balance,
+			0u32.into(),
+			0u32.into(),
+			None,
+		);
+
+		// The pool is destroyed once all liquidity is removed
+		assert!(!LiquidityPools::<T>::contains_key(AssetPair::new(asset_a.into(), asset_b.into())));
+	}
+
+	#[benchmark]
+	fn swap() {
+		let asset_a: AssetIdOf<T> = 1u32;
+		let asset_b: AssetIdOf<T> = 2u32;
+		let caller: T::AccountId = whitelisted_caller();
+		create_asset::<T>(asset_a, &caller);
+		create_asset::<T>(asset_b, &caller);
+		create_pool::<T>(&caller, asset_a, asset_b);
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(caller.clone()), asset_a.into(), asset_b.into(), 1_000u32.into(), None);
+
+		let caller_asset_b_balance = T::Fungibles::balance(asset_b.clone(), &caller);
+		assert!(caller_asset_b_balance > 999_800_000u32.into());
+	}
+
+	#[benchmark]
+	fn swap_exact_tokens_for_tokens() {
+		let asset_a: AssetIdOf<T> = 1u32;
+		let asset_b: AssetIdOf<T> = 2u32;
+		let caller: T::AccountId = whitelisted_caller();
+		create_asset::<T>(asset_a, &caller);
+		create_asset::<T>(asset_b, &caller);
+		create_pool::<T>(&caller, asset_a, asset_b);
+
+		#[extrinsic_call]
+		_(
+			RawOrigin::Signed(caller.clone()),
+			asset_a.into(),
+			asset_b.into(),
+			1_000u32.into(),
+			1u32.into(),
+			None,
+		);
 
 		let caller_asset_b_balance = T::Fungibles::balance(asset_b.clone(), &caller);
-		assert_eq!(caller_asset_b_balance, 999_800_000u32.into());
+		assert!(caller_asset_b_balance > 999_800_000u32.into());
+	}
+
+	#[benchmark]
+	fn swap_tokens_for_exact_tokens() {
+		let asset_a: AssetIdOf<T> = 1u32;
+		let asset_b: AssetIdOf<T> = 2u32;
+		let caller: T::AccountId = whitelisted_caller();
+		create_asset::<T>(asset_a, &caller);
+		create_asset::<T>(asset_b, &caller);
+		create_pool::<T>(&caller, asset_a, asset_b);
+
+		#[extrinsic_call]
+		_(
+			RawOrigin::Signed(caller.clone()),
+			asset_a.into(),
+			asset_b.into(),
+			1_000u32.into(),
+			10_000u32.into(),
+			None,
+		);
+
+		let caller_asset_b_balance = T::Fungibles::balance(asset_b.clone(), &caller);
+		assert_eq!(caller_asset_b_balance, 999_801_000u32.into());
+	}
+
+	#[benchmark]
+	fn swap_along_path(n: Linear<2, { T::MaxHops::get() }>) {
+		let caller: T::AccountId = whitelisted_caller();
+		let path: Vec<AssetIdOf<T>> = (1..=n).collect();
+		for asset in path.iter() {
+			create_asset::<T>(*asset, &caller);
+		}
+		for hop in path.windows(2) {
+			create_pool::<T>(&caller, hop[0], hop[1]);
+		}
+		let asset_out = path[path.len() - 1];
+		let asset_out_balance = T::Fungibles::balance(asset_out, &caller);
+
+		#[extrinsic_call]
+		_(
+			RawOrigin::Signed(caller.clone()),
+			BoundedVec::truncate_from(path.into_iter().map(Into::into).collect()),
+			1_000u32.into(),
+			1u32.into(),
+			None,
+		);
+
+		assert!(T::Fungibles::balance(asset_out, &caller) > asset_out_balance);
+	}
+
+	#[benchmark]
+	fn set_protocol_fee() {
+		let caller: T::AccountId = whitelisted_caller();
+		let protocol_fee = Some((caller, Perbill::from_percent(20)));
+
+		#[extrinsic_call]
+		_(RawOrigin::Root, protocol_fee.clone());
+
+		assert_eq!(ProtocolFee::<T>::get(), protocol_fee);
+	}
+
+	#[benchmark]
+	fn flash_swap() {
+		let asset_a: AssetIdOf<T> = 1u32;
+		let asset_b: AssetIdOf<T> = 2u32;
+		let caller: T::AccountId = whitelisted_caller();
+		create_asset::<T>(asset_a, &caller);
+		create_asset::<T>(asset_b, &caller);
+		create_pool::<T>(&caller, asset_a, asset_b);
+
+		let pool_key = AssetPair::new(asset_a.into(), asset_b.into());
+		let pool = LiquidityPools::<T>::get(pool_key.clone()).unwrap();
+		// Pay for the flash swap up front, so that it succeeds without any work of the callback,
+		// whose weight is charged on top
+		assert_ok!(NativeOrAssetAdapter::<T>::transfer(
+			asset_b.into(),
+			&caller,
+			&pool.manager,
+			2_000u32.into(),
+			Preservation::Expendable,
+		));
+
+		#[extrinsic_call]
+		_(
+			RawOrigin::Signed(caller.clone()),
+			asset_a.into(),
+			asset_b.into(),
+			0u32.into(),
+			1_000u32.into(),
+			None,
+		);
+
+		// Paid back with twice the amount borrowed
+		let asset_b_balance = LiquidityPools::<T>::get(pool_key).unwrap().asset_b_balance;
+		assert_eq!(asset_b_balance, pool.asset_b_balance.saturating_add(1_000u32.into()));
+		assert!(!FlashSwapLock::<T>::get());
+	}
+
+	#[benchmark]
+	fn sync() {
+		let asset_a: AssetIdOf<T> = 1u32;
+		let asset_b: AssetIdOf<T> = 2u32;
+		let caller: T::AccountId = whitelisted_caller();
+		create_asset::<T>(asset_a, &caller);
+		create_asset::<T>(asset_b, &caller);
+		create_pool::<T>(&caller, asset_a, asset_b);
+		let pool_key = AssetPair::new(asset_a.into(), asset_b.into());
+		let pool = LiquidityPools::<T>::get(pool_key.clone()).unwrap();
+		let _ = T::Fungibles::mint_into(asset_a, &pool.manager, 1_000u32.into());
+		let _ = T::Fungibles::mint_into(asset_b, &pool.manager, 1_000u32.into());
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(caller.clone()), asset_a.into(), asset_b.into());
+
+		let pool = LiquidityPools::<T>::get(pool_key).unwrap();
+		assert_eq!(pool.asset_a_balance, 101_000u32.into());
+		assert_eq!(pool.asset_b_balance, 201_000u32.into());
+	}
+
+	#[benchmark]
+	fn skim() {
+		let asset_a: AssetIdOf<T> = 1u32;
+		let asset_b: AssetIdOf<T> = 2u32;
+		let caller: T::AccountId = whitelisted_caller();
+		create_asset::<T>(asset_a, &caller);
+		create_asset::<T>(asset_b, &caller);
+		create_pool::<T>(&caller, asset_a, asset_b);
+		let pool =
+			LiquidityPools::<T>::get(AssetPair::new(asset_a.into(), asset_b.into())).unwrap();
+		let _ = T::Fungibles::mint_into(asset_a, &pool.manager, 1_000u32.into());
+		let _ = T::Fungibles::mint_into(asset_b, &pool.manager, 1_000u32.into());
+		let caller_asset_a_balance = T::Fungibles::balance(asset_a, &caller);
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(caller.clone()), asset_a.into(), asset_b.into(), caller.clone());
+
+		assert_eq!(
+			T::Fungibles::balance(asset_a, &caller),
+			caller_asset_a_balance.saturating_add(1_000u32.into())
+		);
+	}
+
+	#[benchmark]
+	fn pause_pool() -> Result<(), BenchmarkError> {
+		let asset_a: AssetIdOf<T> = 1u32;
+		let asset_b: AssetIdOf<T> = 2u32;
+		let caller: T::AccountId = whitelisted_caller();
+		create_asset::<T>(asset_a, &caller);
+		create_asset::<T>(asset_b, &caller);
+		create_pool::<T>(&caller, asset_a, asset_b);
+		let origin =
+			T::AdminOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+
+		#[extrinsic_call]
+		_(origin as T::RuntimeOrigin, asset_a.into(), asset_b.into());
+
+		assert!(PausedPools::<T>::get(AssetPair::new(asset_a.into(), asset_b.into())));
+		Ok(())
+	}
+
+	#[benchmark]
+	fn unpause_pool() -> Result<(), BenchmarkError> {
+		let asset_a: AssetIdOf<T> = 1u32;
+		let asset_b: AssetIdOf<T> = 2u32;
+		let caller: T::AccountId = whitelisted_caller();
+		create_asset::<T>(asset_a, &caller);
+		create_asset::<T>(asset_b, &caller);
+		create_pool::<T>(&caller, asset_a, asset_b);
+		let pool_key = AssetPair::new(asset_a.into(), asset_b.into());
+		PausedPools::<T>::insert(&pool_key, true);
+		let origin =
+			T::AdminOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+
+		#[extrinsic_call]
+		_(origin as T::RuntimeOrigin, asset_a.into(), asset_b.into());
+
+		assert!(!PausedPools::<T>::get(pool_key));
+		Ok(())
+	}
+
+	#[benchmark]
+	fn set_trading_enabled() -> Result<(), BenchmarkError> {
+		let origin =
+			T::AdminOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+
+		#[extrinsic_call]
+		_(origin as T::RuntimeOrigin, false);
+
+		assert!(!TradingEnabled::<T>::get());
+		Ok(())
+	}
+
+	#[benchmark]
+	fn destroy_pool() {
+		let asset_a: AssetIdOf<T> = 1u32;
+		let asset_b: AssetIdOf<T> = 2u32;
+		let caller: T::AccountId = whitelisted_caller();
+		create_asset::<T>(asset_a, &caller);
+		create_asset::<T>(asset_b, &caller);
+		fund_pool_deposit::<T>(&caller);
+		assert_ok!(Dex::<T>::create_pool(
+			RawOrigin::Signed(caller.clone()).into(),
+			asset_a.into(),
+			asset_b.into(),
+			T::SwapFeeTiers::get()[0],
+		));
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(caller.clone()), asset_a.into(), asset_b.into());
+
+		assert!(!LiquidityPools::<T>::contains_key(AssetPair::new(asset_a.into(), asset_b.into())));
+		assert!(<T::NativeBalance as fungible::hold::Inspect<_>>::balance_on_hold(
+			&HoldReason::PoolDeposit.into(),
+			&caller
+		)
+		.is_zero());
 	}
 
 	impl_benchmark_test_suite!(
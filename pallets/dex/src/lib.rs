@@ -8,8 +8,10 @@ use frame_support::traits::fungibles;
 use frame_support::traits::fungibles::*;
 use frame_support::PalletId;
 pub use pallet::*;
-use sp_runtime::Perbill;
+use sp_runtime::{FixedU128, Perbill, Permill};
 
+mod adapter;
+pub mod migrations;
 mod types;
 mod util;
 
@@ -18,7 +20,11 @@ pub(crate) mod tests;
 
 #[cfg(feature = "runtime-benchmarks")]
 mod benchmarking;
-mod traits;
+pub mod traits;
+pub mod weights;
+pub use adapter::{EnsureNotLpAssetId, NativeOrAssetAdapter};
+pub use types::{NativeOrAssetId, PriceObservation};
+pub use weights::WeightInfo;
 
 pub type AccountIdOf<T> = <T as frame_system::Config>::AccountId;
 pub type AssetIdOf<T> = <<T as Config>::Fungibles as fungibles::Inspect<
@@ -33,93 +39,342 @@ pub type AssetBalanceOf<T> = <<T as Config>::Fungibles as fungibles::Inspect<
 	<T as frame_system::Config>::AccountId,
 >>::Balance;
 
+pub type NativeOrAssetIdOf<T> = NativeOrAssetId<AssetIdOf<T>>;
+
+pub(crate) const LOG_TARGET: &str = "runtime::dex";
+
 #[frame_support::pallet]
 pub mod pallet {
+	use crate::traits::FlashSwapCallback as _;
 	use crate::types::AssetPair;
 	use crate::*;
+	use frame_support::storage::with_storage_layer;
 	use frame_support::traits::{
-		fungible::{self},
+		fungible::{self, MutateHold},
 		fungibles::{self},
+		tokens::{Precision, Preservation},
+		UnixTime,
 	};
 	use frame_system::pallet_prelude::*;
-	use sp_runtime::traits::{AccountIdConversion, Bounded};
+	use sp_runtime::traits::{AccountIdConversion, CheckedDiv, CheckedMul, UniqueSaturatedInto};
+	use sp_runtime::FixedPointNumber;
+	use sp_std::vec::Vec;
+
+	/// The in-code storage version.
+	const STORAGE_VERSION: StorageVersion = StorageVersion::new(2);
 
 	#[pallet::pallet]
+	#[pallet::storage_version(STORAGE_VERSION)]
 	pub struct Pallet<T>(_);
 
 	#[pallet::config]
 	pub trait Config: frame_system::Config {
 		type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
 
+		/// Overarching hold reason, used for the pool creation deposit.
+		type RuntimeHoldReason: From<HoldReason>;
+
 		/// Type to access the Balances Pallet.
 		type NativeBalance: fungible::Inspect<Self::AccountId>
 			+ fungible::Mutate<Self::AccountId>
-			+ fungible::hold::Inspect<Self::AccountId>
-			+ fungible::hold::Mutate<Self::AccountId>
+			+ fungible::hold::Inspect<Self::AccountId, Reason = Self::RuntimeHoldReason>
+			+ fungible::hold::Mutate<Self::AccountId, Reason = Self::RuntimeHoldReason>
 			+ fungible::freeze::Inspect<Self::AccountId>
 			+ fungible::freeze::Mutate<Self::AccountId>;
 
-		/// Type to access the Assets Pallet.
-		type Fungibles: fungibles::Inspect<Self::AccountId, AssetId = u32>
+		/// Type to access the Assets Pallet. Its balance type is shared with `NativeBalance` so
+		/// that the native currency can be pooled with the assets.
+		type Fungibles: fungibles::Inspect<Self::AccountId, AssetId = u32, Balance = BalanceOf<Self>>
 			+ fungibles::Mutate<Self::AccountId>
-			+ fungibles::Create<Self::AccountId>;
+			+ fungibles::Create<Self::AccountId>
+			+ fungibles::metadata::Inspect<Self::AccountId>;
 
 		#[pallet::constant]
 		type PalletId: Get<PalletId>;
 
+		/// Number of decimals of the native currency, the assets take theirs from their metadata.
 		#[pallet::constant]
 		type TokenDecimals: Get<u32>;
 
 		#[pallet::constant]
 		type MinimumLiquidity: Get<u32>;
+
+		/// Maximum number of assets in a path for routed swaps.
+		#[pallet::constant]
+		type MaxHops: Get<u32>;
+
+		/// Maximum number of liquidity pools `OraclePrice::find_best_route` reads, so that other
+		/// pallets can charge for it. The `DexApi` runtime API reads every pool.
+		#[pallet::constant]
+		type MaxPoolsScanned: Get<u32>;
+
+		/// First asset id of the range reserved for LP tokens.
+		#[pallet::constant]
+		type MinLpAssetId: Get<AssetIdOf<Self>>;
+
+		/// Last asset id of the range reserved for LP tokens.
+		#[pallet::constant]
+		type MaxLpAssetId: Get<AssetIdOf<Self>>;
+
+		/// Maximum number of asset ids tried for the LP token of a new pool, as ids taken by other
+		/// assets are skipped. `create_pool` is charged a read for each of them.
+		#[pallet::constant]
+		type MaxLpAssetIdProbes: Get<u32>;
+
+		/// Swap fee tiers that pool creators can choose from.
+		#[pallet::constant]
+		type SwapFeeTiers: Get<Vec<Permill>>;
+
+		/// Amount of the native currency held from the creator of a pool until it's destroyed.
+		#[pallet::constant]
+		type PoolDeposit: Get<BalanceOf<Self>>;
+
+		/// Time provider for the price accumulators of the pools.
+		type Time: UnixTime;
+
+		/// Maximum number of price observations kept per pool for the TWAP oracle.
+		#[pallet::constant]
+		type MaxObservations: Get<u32>;
+
+		/// Minimum number of seconds between two price observations recorded for a pool.
+		#[pallet::constant]
+		type ObservationPeriod: Get<u64>;
+
+		/// Handler paying for flash swaps after receiving their output.
+		type FlashSwapCallback: traits::FlashSwapCallback<
+			AccountIdOf<Self>,
+			NativeOrAssetIdOf<Self>,
+			AssetBalanceOf<Self>,
+		>;
+
+		/// Origin allowed to pause and unpause pools and to switch trading off in all of them.
+		type AdminOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+
+		/// Weight information for extrinsics in this pallet.
+		type WeightInfo: WeightInfo;
+	}
+
+	/// Reasons for the pallet to hold funds.
+	#[pallet::composite_enum]
+	pub enum HoldReason {
+		/// Deposit for creating a liquidity pool.
+		PoolDeposit,
 	}
 
 	#[pallet::storage]
 	pub type LiquidityPools<T: Config> =
 		StorageMap<_, Blake2_128Concat, AssetPair<T>, LiquidityPool<T>>;
 
+	/// Asset id to try first for the LP token of the next pool, once a pool was created or taken ids
+	/// were skipped
+	#[pallet::storage]
+	pub type NextLpAssetId<T: Config> = StorageValue<_, AssetIdOf<T>>;
+
+	/// Account receiving the protocol fee and its share of the LP fee growth, if it's switched on
+	#[pallet::storage]
+	pub type ProtocolFee<T: Config> = StorageValue<_, (AccountIdOf<T>, Perbill)>;
+
+	/// Price accumulators of each pool recorded at most every `ObservationPeriod`, oldest first
+	#[pallet::storage]
+	pub type PriceObservations<T: Config> = StorageMap<
+		_,
+		Blake2_128Concat,
+		AssetPair<T>,
+		BoundedVec<PriceObservation, T::MaxObservations>,
+		ValueQuery,
+	>;
+
+	/// Set while the callback of a flash swap runs, so that it can't use the liquidity pools
+	#[pallet::storage]
+	pub type FlashSwapLock<T: Config> = StorageValue<_, bool, ValueQuery>;
+
+	/// Pools paused by `AdminOrigin`, from which liquidity can only be removed
+	#[pallet::storage]
+	pub type PausedPools<T: Config> =
+		StorageMap<_, Blake2_128Concat, AssetPair<T>, bool, ValueQuery>;
+
+	#[pallet::type_value]
+	pub fn DefaultTradingEnabled() -> bool {
+		true
+	}
+
+	/// Whether the pools can be traded in and added to, which `AdminOrigin` can switch off for all
+	/// of them at once
+	#[pallet::storage]
+	pub type TradingEnabled<T: Config> = StorageValue<_, bool, ValueQuery, DefaultTradingEnabled>;
+
 	#[pallet::genesis_config]
 	#[derive(frame_support::DefaultNoBound)]
 	pub struct GenesisConfig<T: Config> {
-		#[serde(skip)]
-		_config: core::marker::PhantomData<T>,
+		/// The pools to create at genesis, given as `(lp_asset_id, asset_a, asset_b, amount_a,
+		/// amount_b, provider)`. Each pool gets the first of the `SwapFeeTiers` and the LP token
+		/// `lp_asset_id`, which has to lie in the range reserved for LP tokens. The provider pays
+		/// the `PoolDeposit` and adds `amount_a` and `amount_b` as the initial liquidity. The
+		/// amounts are `u128`, as the balance type of the pallet can't be deserialized.
+		pub pools: Vec<(
+			AssetIdOf<T>,
+			NativeOrAssetIdOf<T>,
+			NativeOrAssetIdOf<T>,
+			u128,
+			u128,
+			AccountIdOf<T>,
+		)>,
 	}
 
 	#[pallet::genesis_build]
 	impl<T: Config> BuildGenesisConfig for GenesisConfig<T> {
 		fn build(&self) {
-			// Initialize pallet balance for creating and minting LP Tokens
-			use frame_support::traits::fungible::*;
-			let account_id = T::PalletId::get().into_account_truncating();
-			let _ = T::NativeBalance::mint_into(
-				&account_id,
-				BalanceOf::<T>::max_value() / 10_000u32.into(),
-			);
+			let fee_tier = T::SwapFeeTiers::get()
+				.first()
+				.copied()
+				.expect("At least one fee tier is allowed");
+			let lp_asset_ids = T::MinLpAssetId::get()..=T::MaxLpAssetId::get();
+			let balance = |amount: u128| -> AssetBalanceOf<T> {
+				amount
+					.try_into()
+					.unwrap_or_else(|_| panic!("The liquidity of a genesis pool overflows"))
+			};
+			for (lp_asset_id, asset_a, asset_b, amount_a, amount_b, provider) in &self.pools {
+				assert!(
+					lp_asset_ids.contains(lp_asset_id),
+					"The LP token of a genesis pool is outside of the range reserved for LP tokens"
+				);
+				Pallet::<T>::do_create_pool(
+					provider,
+					*asset_a,
+					*asset_b,
+					fee_tier,
+					Some(*lp_asset_id),
+				)
+				.expect("Genesis pools can be created");
+				let (amount_a, amount_b) = (balance(*amount_a), balance(*amount_b));
+				Pallet::<T>::do_mint(
+					provider, *asset_a, *asset_b, amount_a, amount_b, amount_a, amount_b,
+				)
+				.expect("Genesis pool providers hold the initial liquidity");
+			}
 		}
 	}
 
 	#[pallet::event]
 	#[pallet::generate_deposit(pub(super) fn deposit_event)]
 	pub enum Event<T: Config> {
-		/// Event for a new liquidity pool creation
-		LiquidityPoolCreated(AssetIdOf<T>, AssetIdOf<T>, AssetIdOf<T>),
-		/// Event for adding a liquidity to an existing pool
-		LiquidityAdded(AssetIdOf<T>, AssetIdOf<T>, AssetBalanceOf<T>, AssetBalanceOf<T>),
-		/// Event for removing a liquidity from an existing pool
-		LiquidityRemoved(AssetIdOf<T>, AssetIdOf<T>, AssetBalanceOf<T>),
-		/// Event for swapping first asset for second asset for the provided input amount
-		Swapped(AssetIdOf<T>, AssetIdOf<T>, AssetBalanceOf<T>),
+		/// A liquidity pool was created by `creator`, with `pool_id` as the asset id of its LP token
+		LiquidityPoolCreated {
+			creator: AccountIdOf<T>,
+			pool_id: AssetIdOf<T>,
+			asset_a: NativeOrAssetIdOf<T>,
+			asset_b: NativeOrAssetIdOf<T>,
+			fee: Permill,
+		},
+		/// `who` added liquidity to a pool and received `lp_minted` LP tokens
+		LiquidityAdded {
+			who: AccountIdOf<T>,
+			pool_id: AssetIdOf<T>,
+			asset_a: NativeOrAssetIdOf<T>,
+			asset_b: NativeOrAssetIdOf<T>,
+			amount_a: AssetBalanceOf<T>,
+			amount_b: AssetBalanceOf<T>,
+			lp_minted: AssetBalanceOf<T>,
+			reserve_a: AssetBalanceOf<T>,
+			reserve_b: AssetBalanceOf<T>,
+		},
+		/// `who` burned `lp_burned` LP tokens to remove liquidity from a pool
+		LiquidityRemoved {
+			who: AccountIdOf<T>,
+			pool_id: AssetIdOf<T>,
+			asset_a: NativeOrAssetIdOf<T>,
+			asset_b: NativeOrAssetIdOf<T>,
+			amount_a: AssetBalanceOf<T>,
+			amount_b: AssetBalanceOf<T>,
+			lp_burned: AssetBalanceOf<T>,
+			reserve_a: AssetBalanceOf<T>,
+			reserve_b: AssetBalanceOf<T>,
+		},
+		/// `who` swapped `amount_in` of `asset_in` for `amount_out` of `asset_out` in a pool
+		Swapped {
+			who: AccountIdOf<T>,
+			pool_id: AssetIdOf<T>,
+			asset_in: NativeOrAssetIdOf<T>,
+			asset_out: NativeOrAssetIdOf<T>,
+			amount_in: AssetBalanceOf<T>,
+			amount_out: AssetBalanceOf<T>,
+			reserve_in: AssetBalanceOf<T>,
+			reserve_out: AssetBalanceOf<T>,
+		},
+		/// `who` swapped along a path of pools with the input and the final output amount
+		PathSwapped {
+			who: AccountIdOf<T>,
+			path: BoundedVec<NativeOrAssetIdOf<T>, T::MaxHops>,
+			amount_in: AssetBalanceOf<T>,
+			amount_out: AssetBalanceOf<T>,
+		},
+		/// The protocol fee was switched on with a recipient and share, or off
+		ProtocolFeeSet { protocol_fee: Option<(AccountIdOf<T>, Perbill)> },
+		/// `who` received `amount_a_out` and `amount_b_out` from a pool before paying back
+		/// `amount_a_in` and `amount_b_in`
+		FlashSwapped {
+			who: AccountIdOf<T>,
+			pool_id: AssetIdOf<T>,
+			asset_a: NativeOrAssetIdOf<T>,
+			asset_b: NativeOrAssetIdOf<T>,
+			amount_a_out: AssetBalanceOf<T>,
+			amount_b_out: AssetBalanceOf<T>,
+			amount_a_in: AssetBalanceOf<T>,
+			amount_b_in: AssetBalanceOf<T>,
+			reserve_a: AssetBalanceOf<T>,
+			reserve_b: AssetBalanceOf<T>,
+		},
+		/// `who` set the reserves of a pool to the balances of its account
+		Synced {
+			who: AccountIdOf<T>,
+			pool_id: AssetIdOf<T>,
+			asset_a: NativeOrAssetIdOf<T>,
+			asset_b: NativeOrAssetIdOf<T>,
+			reserve_a: AssetBalanceOf<T>,
+			reserve_b: AssetBalanceOf<T>,
+		},
+		/// `who` sent the balances of a pool account above its reserves to `to`
+		Skimmed {
+			who: AccountIdOf<T>,
+			to: AccountIdOf<T>,
+			pool_id: AssetIdOf<T>,
+			asset_a: NativeOrAssetIdOf<T>,
+			asset_b: NativeOrAssetIdOf<T>,
+			amount_a: AssetBalanceOf<T>,
+			amount_b: AssetBalanceOf<T>,
+		},
+		/// A pool was paused, so that liquidity can only be removed from it
+		PoolPaused {
+			pool_id: AssetIdOf<T>,
+			asset_a: NativeOrAssetIdOf<T>,
+			asset_b: NativeOrAssetIdOf<T>,
+		},
+		/// A paused pool was unpaused
+		PoolUnpaused {
+			pool_id: AssetIdOf<T>,
+			asset_a: NativeOrAssetIdOf<T>,
+			asset_b: NativeOrAssetIdOf<T>,
+		},
+		/// Trading in all pools was switched on or off
+		TradingEnabledSet { enabled: bool },
+		/// `who` destroyed a pool that never had liquidity and got its creation deposit back
+		LiquidityPoolDestroyed {
+			who: AccountIdOf<T>,
+			pool_id: AssetIdOf<T>,
+			asset_a: NativeOrAssetIdOf<T>,
+			asset_b: NativeOrAssetIdOf<T>,
+		},
 	}
 
 	#[pallet::error]
 	pub enum Error<T> {
 		/// There is not asset with the provided AssetId
 		UnknownAssetId,
-		/// Unable to mint the LP token because there is already an asset with the provided AssetId
-		AssetIdExists,
 		/// Liquidity Pool does not exist
 		LiquidityPoolDoesNotExist,
-		/// Overflow for asset id counter
+		/// There is no free asset id left in the range reserved for LP tokens, or none within
+		/// `MaxLpAssetIdProbes` ids
 		AssetLimitReached,
 		/// Arithmetic Error when multiplying and dividing
 		Arithmetic,
@@ -131,106 +386,118 @@ pub mod pallet {
 		InsufficientBurnBalance,
 		/// Provided assets are the same
 		IdenticalAssets,
+		/// The swap would return less than the minimum accepted output amount
+		InsufficientOutputAmount,
+		/// The swap would require more than the maximum accepted input amount
+		ExcessiveInputAmount,
+		/// The provided swap path has less than two assets
+		InvalidPath,
+		/// There is no route of liquidity pools between the provided assets
+		NoRouteFound,
+		/// The deadline of the call has passed
+		Expired,
+		/// The provided swap fee is not one of the allowed fee tiers
+		UnsupportedFeeTier,
+		/// There is already a liquidity pool for the provided assets
+		LiquidityPoolExists,
+		/// The amount of `asset_a` added or removed is below the accepted minimum
+		InsufficientAmountA,
+		/// The amount of `asset_b` added or removed is below the accepted minimum
+		InsufficientAmountB,
+		/// There is no price observation of the pool old enough for the requested window
+		NoPriceObservation,
+		/// The constant product of the pool including the fee would decrease
+		InvariantViolated,
+		/// The liquidity pools can't be used during the callback of a flash swap
+		FlashSwapInProgress,
+		/// The pool keeps its assets in the pallet account shared with other pools
+		SharedPoolAccount,
+		/// The pool is paused, liquidity can only be removed from it
+		PoolPaused,
+		/// The pool isn't paused
+		PoolNotPaused,
+		/// Trading is switched off in all pools, liquidity can only be removed from them
+		TradingDisabled,
+		/// Only the account that created the pool can destroy it
+		NotPoolDepositor,
+		/// The pool has liquidity, it's destroyed when the last of it is burned
+		PoolNotEmpty,
+	}
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+		fn on_idle(_n: BlockNumberFor<T>, remaining_weight: Weight) -> Weight {
+			Self::skip_taken_lp_asset_ids(remaining_weight)
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn try_state(_n: BlockNumberFor<T>) -> Result<(), sp_runtime::TryRuntimeError> {
+			Self::do_try_state()
+		}
 	}
 
 	#[pallet::call]
 	impl<T: Config> Pallet<T> {
 		/// The `mint` function allows a user to add liquidity to a liquidity pool.
-		/// Given two assets and their amounts, it either creates a new liquidity pool if
-		/// it does not already exist for these two assets or adds the provided liquidity
-		/// to an existing pool. The user will receive LP tokens in return.
+		/// Given two assets and their desired amounts, it adds liquidity to the pool of these
+		/// two assets, which has to be created with `create_pool` first. Like the Uniswap V2
+		/// router, only the amounts that match the current pool ratio are taken from the user, so
+		/// at most one of the desired amounts is used in full. The user will receive LP tokens in
+		/// return. Either asset can be the native currency, given as `NativeOrAssetId::Native`.
 		///
 		/// # Arguments
 		///
 		/// * `origin` - The origin caller of this function. This should be signed by the user adding liquidity.
 		/// * `asset_a` - The identifier for the first type of asset that the user wants to provide.
 		/// * `asset_b` - The identifier for the second type of asset that the user wants to provide.
-		/// * `amount_a` - The amount of `asset_a` that the user is providing.
-		/// * `amount_b` - The amount of `asset_b` that the user is providing.
+		/// * `amount_a_desired` - The maximum amount of `asset_a` that the user is providing.
+		/// * `amount_b_desired` - The maximum amount of `asset_b` that the user is providing.
+		/// * `amount_a_min` - The minimum amount of `asset_a` that the user accepts to provide.
+		/// * `amount_b_min` - The minimum amount of `asset_b` that the user accepts to provide.
+		/// * `deadline` - The last block number at which the call can be executed, if any.
 		///
 		/// # Errors
 		///
 		/// This function will return an error in the following scenarios:
 		///
 		/// * If the origin is not signed (i.e., the function was not called by a user).
+		/// * If the current block is past the `deadline` (`Expired`).
 		/// * If the provided assets do not exist.
 		/// * If `asset_a` and `asset_b` are the same.
-		/// * If `amount_a` or `amount_b` is 0 or less.
-		/// * If creating a new liquidity pool would exceed the maximum number of allowed assets (`AssetLimitReached`).
+		/// * If `amount_a_desired` or `amount_b_desired` is 0 or less.
+		/// * If the liquidity pool for the given asset pair does not exist.
+		/// * If the amount of `asset_a` or `asset_b` matching the pool ratio is below its minimum
+		///   (`InsufficientAmountA`, `InsufficientAmountB`).
 		/// * If adding liquidity to the pool fails for any reason due to arithmetic overflows or underflows
 		///
 		/// # Events
 		///
-		/// If the function succeeds, it triggers two events:
-		///
-		/// * `LiquidityPoolCreated(asset_a, asset_b)` if a new liquidity pool was created.
-		/// * `LiquidityAdded(asset_a, asset_b, amount_a, amount_b)` after the liquidity has been successfully added.
+		/// If the function succeeds, it triggers a `LiquidityAdded` event with the amounts that were actually
+		/// added and the LP tokens minted.
 		///
 		#[pallet::call_index(0)]
-		#[pallet::weight(Weight::default())]
+		#[pallet::weight(T::WeightInfo::mint())]
 		pub fn mint(
 			origin: OriginFor<T>,
-			lp_asset_id: AssetIdOf<T>,
-			asset_a: AssetIdOf<T>,
-			asset_b: AssetIdOf<T>,
-			amount_a: AssetBalanceOf<T>,
-			amount_b: AssetBalanceOf<T>,
+			asset_a: NativeOrAssetIdOf<T>,
+			asset_b: NativeOrAssetIdOf<T>,
+			amount_a_desired: AssetBalanceOf<T>,
+			amount_b_desired: AssetBalanceOf<T>,
+			amount_a_min: AssetBalanceOf<T>,
+			amount_b_min: AssetBalanceOf<T>,
+			deadline: Option<BlockNumberFor<T>>,
 		) -> DispatchResult {
 			let who = ensure_signed(origin)?;
-			Self::ensure_assets_exist(asset_a, asset_b)?;
-			// Assets should be different to create a pool
-			ensure!(asset_a != asset_b, Error::<T>::IdenticalAssets);
-			ensure!(
-				amount_a > AssetBalanceOf::<T>::zero() && amount_b > AssetBalanceOf::<T>::zero(),
-				Error::<T>::InsufficientInputAmount
-			);
-			ensure!(T::Fungibles::asset_exists(lp_asset_id) == false, Error::<T>::AssetIdExists);
-
-			let pool_asset_pair = AssetPair::new(asset_a.clone(), asset_b.clone());
-			let pallet_id: T::AccountId = T::PalletId::get().into_account_truncating();
-
-			let mut pool = match LiquidityPools::<T>::get(pool_asset_pair.clone()) {
-				Some(existing_pool) => existing_pool,
-				None => {
-					// Create the asset with a specific asset_id
-					T::Fungibles::create(
-						lp_asset_id.clone(),
-						pallet_id.clone(),
-						true,
-						AssetBalanceOf::<T>::one(),
-					)?;
-
-					// Create the liquidity pool if it doesn't exist
-					let new_pool = LiquidityPool {
-						id: lp_asset_id.clone(),
-						manager: pallet_id,
-						asset_a_balance: AssetBalanceOf::<T>::zero(),
-						asset_b_balance: AssetBalanceOf::<T>::zero(),
-					};
-
-					Self::deposit_event(crate::pallet::Event::LiquidityPoolCreated(
-						lp_asset_id,
-						pool_asset_pair.asset_a,
-						pool_asset_pair.asset_b,
-					));
-
-					new_pool
-				},
-			};
-
-			// Add liquidity
-			pool.add_liquidity(&pool_asset_pair, amount_a, amount_b, &who)?;
-			// Set or update the existing storage with new balances
-			<LiquidityPools<T>>::set(&pool_asset_pair, Some(pool.clone()));
-
-			Self::deposit_event(Event::LiquidityAdded(
-				pool_asset_pair.asset_a.clone(),
-				pool_asset_pair.asset_b.clone(),
-				amount_a,
-				amount_b,
-			));
-
-			Ok(())
+			Self::ensure_not_expired(deadline)?;
+			Self::do_mint(
+				&who,
+				asset_a,
+				asset_b,
+				amount_a_desired,
+				amount_b_desired,
+				amount_a_min,
+				amount_b_min,
+			)
 		}
 
 		/// The `burn` function allows a user to remove liquidity from a specified
@@ -243,31 +510,43 @@ pub mod pallet {
 		/// * `asset_a` - The identifier for the first type of asset in the liquidity pool.
 		/// * `asset_b` - The identifier for the second type of asset in the liquidity pool.
 		/// * `token_amount` - The amount of liquidity the user wants to remove. This is denominated in LP tokens.
+		/// * `amount_a_min` - The minimum amount of `asset_a` that the user accepts to receive.
+		/// * `amount_b_min` - The minimum amount of `asset_b` that the user accepts to receive.
+		/// * `deadline` - The last block number at which the call can be executed, if any.
 		///
 		/// # Errors
 		///
 		/// This function will return an error in the following scenarios:
 		///
 		/// * If the origin is not signed (i.e., the function was not called by a user).
+		/// * If the current block is past the `deadline` (`Expired`).
 		/// * If the provided assets do not exist.
 		/// * If `token_amount` is 0 or if it's more than the LP Token balance of the caller
 		/// * If `asset_a` and `asset_b` are the same.
 		/// * If the liquidity pool for the given asset pair does not exist.
+		/// * If the amount of `asset_a` or `asset_b` returned is below its minimum
+		///   (`InsufficientAmountA`, `InsufficientAmountB`).
 		/// * If removing the liquidity from the pool fails for any reason due to arithmetic overflow or underflow
 		///
 		/// # Events
 		///
-		/// If the function succeeds, it triggers a `LiquidityRemoved(asset_a, asset_b, token_amount)` event.
+		/// If the function succeeds, it triggers a `LiquidityRemoved` event with the amounts returned.
+		/// When all liquidity but the minimum locked by the first mint is removed, the caller also
+		/// gets the reserves backing it, and the pool is destroyed and its creation deposit released.
 		///
 		#[pallet::call_index(1)]
-		#[pallet::weight(Weight::default())]
+		#[pallet::weight(T::WeightInfo::burn_partial().max(T::WeightInfo::burn_full()))]
 		pub fn burn(
 			origin: OriginFor<T>,
-			asset_a: AssetIdOf<T>,
-			asset_b: AssetIdOf<T>,
+			asset_a: NativeOrAssetIdOf<T>,
+			asset_b: NativeOrAssetIdOf<T>,
 			token_amount: AssetBalanceOf<T>,
+			amount_a_min: AssetBalanceOf<T>,
+			amount_b_min: AssetBalanceOf<T>,
+			deadline: Option<BlockNumberFor<T>>,
 		) -> DispatchResult {
 			let who = ensure_signed(origin)?;
+			Self::ensure_not_expired(deadline)?;
 			Self::ensure_assets_exist(asset_a.clone(), asset_b.clone())?;
 			ensure!(asset_a != asset_b, Error::<T>::IdenticalAssets);
 			// Make sure the pool exists
@@ -275,22 +554,33 @@ pub mod pallet {
 			let mut pool = LiquidityPools::<T>::get(pool_asset_pair.clone())
 				.ok_or_else(|| DispatchError::from(Error::<T>::LiquidityPoolDoesNotExist))?;
 
-			pool.remove_liquidity(&pool_asset_pair, token_amount, &who)?;
+			let (amount_a, amount_b) = pool.remove_liquidity(
+				&pool_asset_pair,
+				token_amount,
+				amount_a_min,
+				amount_b_min,
+				&who,
+			)?;
 
 			// Clear up the pool if all liquidity is removed
-			let destroy_balance: AssetBalanceOf<T> = T::MinimumLiquidity::get().into();
-			if pool.asset_a_balance == destroy_balance && pool.asset_b_balance == destroy_balance {
-				<LiquidityPools<T>>::remove(&pool_asset_pair);
+			if pool.only_locked_liquidity_left() {
+				Self::remove_pool(&pool_asset_pair, &pool)?;
 			} else {
 				// Update the existing storage with new balances
 				<LiquidityPools<T>>::set(&pool_asset_pair, Some(pool.clone()));
 			}
 
-			Self::deposit_event(Event::LiquidityRemoved(
-				asset_a.clone(),
-				asset_b.clone(),
-				token_amount,
-			));
+			Self::deposit_event(Event::LiquidityRemoved {
+				who,
+				pool_id: pool.id,
+				asset_a: pool_asset_pair.asset_a,
+				asset_b: pool_asset_pair.asset_b,
+				amount_a,
+				amount_b,
+				lp_burned: token_amount,
+				reserve_a: pool.asset_a_balance,
+				reserve_b: pool.asset_b_balance,
+			});
 			Ok(())
 		}
 
@@ -303,12 +593,14 @@ pub mod pallet {
 		/// * `asset_in` - The identifier for the type of asset that the user wants to swap from.
 		/// * `asset_out` - The identifier for the type of asset that the user wants to swap to.
 		/// * `amount_in` - The amount of `asset_in` that the user wants to swap.
+		/// * `deadline` - The last block number at which the call can be executed, if any.
 		///
 		/// # Errors
 		///
 		/// This function will return an error in the following scenarios:
 		///
 		/// * If the origin is not signed (i.e., the function was not called by a user).
+		/// * If the current block is past the `deadline` (`Expired`).
 		/// * If the provided assets do not exist.
 		/// * If `amount_in` is 0 or less.
 		/// * If `asset_in` and `asset_out` are the same.
@@ -317,83 +609,1193 @@ pub mod pallet {
 		///
 		/// # Events
 		///
-		/// If the function succeeds, it triggers a `Swapped(asset_a, asset_b, amount_in)` event.
+		/// If the function succeeds, it triggers a `Swapped` event with the input and output amounts.
 		///
 		#[pallet::call_index(2)]
-		#[pallet::weight(Weight::default())]
+		#[pallet::weight(T::WeightInfo::swap())]
 		pub fn swap(
 			origin: OriginFor<T>,
-			asset_in: AssetIdOf<T>,
-			asset_out: AssetIdOf<T>,
+			asset_in: NativeOrAssetIdOf<T>,
+			asset_out: NativeOrAssetIdOf<T>,
 			amount_in: AssetBalanceOf<T>,
+			deadline: Option<BlockNumberFor<T>>,
 		) -> DispatchResult {
 			let who = ensure_signed(origin)?;
-			Self::ensure_assets_exist(asset_in, asset_out)?;
-			ensure!(asset_in != asset_out, Error::<T>::IdenticalAssets);
-			let pool_asset_pair = AssetPair::new(asset_in.clone(), asset_out.clone());
-			let pool = LiquidityPools::<T>::get(pool_asset_pair.clone())
-				.ok_or_else(|| DispatchError::from(Error::<T>::LiquidityPoolDoesNotExist))?;
-
-			// Swapping for asset_in (asset_out) in the pool with amount_in of asset_in
-			pool.swap(
+			Self::ensure_not_expired(deadline)?;
+			Self::do_swap_exact_in(
 				&who,
-				pool_asset_pair.clone(),
-				asset_in.clone(),
-				asset_out.clone(),
+				asset_in,
+				asset_out,
 				amount_in,
+				AssetBalanceOf::<T>::zero(),
 			)?;
+			Ok(())
+		}
 
-			Self::deposit_event(Event::Swapped(
-				pool_asset_pair.asset_a,
-				pool_asset_pair.asset_b,
-				amount_in,
-			));
-
+		/// The `swap_exact_tokens_for_tokens` function allows a user to exchange an exact amount of
+		/// one token for another, while stating the minimum output amount they are willing to accept.
+		/// This protects the user from being front-run or sandwiched by other trades in the same pool.
+		///
+		/// # Arguments
+		///
+		/// * `origin` - The origin caller of this function. This should be signed by the user performing the swap.
+		/// * `asset_in` - The identifier for the type of asset that the user wants to swap from.
+		/// * `asset_out` - The identifier for the type of asset that the user wants to swap to.
+		/// * `amount_in` - The exact amount of `asset_in` that the user wants to swap.
+		/// * `amount_out_min` - The minimum amount of `asset_out` that the user accepts to receive.
+		/// * `deadline` - The last block number at which the call can be executed, if any.
+		///
+		/// # Errors
+		///
+		/// This function will return an error in the following scenarios:
+		///
+		/// * If the origin is not signed (i.e., the function was not called by a user).
+		/// * If the current block is past the `deadline` (`Expired`).
+		/// * If the provided assets do not exist.
+		/// * If `amount_in` is 0 or less.
+		/// * If `asset_in` and `asset_out` are the same.
+		/// * If the liquidity pool for the given pair of assets does not exist.
+		/// * If the output amount is less than `amount_out_min` (`InsufficientOutputAmount`).
+		/// * If the swap operation fails for any reason due to arithmetic error
+		///
+		/// # Events
+		///
+		/// If the function succeeds, it triggers a `Swapped` event with the input and output amounts.
+		///
+		#[pallet::call_index(3)]
+		#[pallet::weight(T::WeightInfo::swap_exact_tokens_for_tokens())]
+		pub fn swap_exact_tokens_for_tokens(
+			origin: OriginFor<T>,
+			asset_in: NativeOrAssetIdOf<T>,
+			asset_out: NativeOrAssetIdOf<T>,
+			amount_in: AssetBalanceOf<T>,
+			amount_out_min: AssetBalanceOf<T>,
+			deadline: Option<BlockNumberFor<T>>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			Self::ensure_not_expired(deadline)?;
+			Self::do_swap_exact_in(&who, asset_in, asset_out, amount_in, amount_out_min)?;
 			Ok(())
 		}
-	}
 
-	impl<T: Config> traits::TokenRatio for Pallet<T> {
-		type AssetId = AssetIdOf<T>;
-		fn ratio(token_a: Self::AssetId, token_b: Self::AssetId) -> Result<Perbill, DispatchError> {
-			let pool_key = AssetPair::new(token_a.clone(), token_b.clone());
-			Self::ensure_assets_exist(token_a, token_b)?;
-			ensure!(token_a != token_b, Error::<T>::IdenticalAssets);
-			let pool = <LiquidityPools<T>>::get(pool_key.clone())
-				.ok_or_else(|| DispatchError::from(Error::<T>::LiquidityPoolDoesNotExist))?;
+		/// The `swap_tokens_for_exact_tokens` function allows a user to receive an exact amount of
+		/// one token in exchange for another, while stating the maximum input amount they are
+		/// willing to pay. The required input includes the fee tier of the pool and is rounded in
+		/// favour of the pool.
+		///
+		/// # Arguments
+		///
+		/// * `origin` - The origin caller of this function. This should be signed by the user performing the swap.
+		/// * `asset_in` - The identifier for the type of asset that the user wants to swap from.
+		/// * `asset_out` - The identifier for the type of asset that the user wants to swap to.
+		/// * `amount_out` - The exact amount of `asset_out` that the user wants to receive.
+		/// * `amount_in_max` - The maximum amount of `asset_in` that the user accepts to pay.
+		/// * `deadline` - The last block number at which the call can be executed, if any.
+		///
+		/// # Errors
+		///
+		/// This function will return an error in the following scenarios:
+		///
+		/// * If the origin is not signed (i.e., the function was not called by a user).
+		/// * If the current block is past the `deadline` (`Expired`).
+		/// * If the provided assets do not exist.
+		/// * If `amount_out` is 0 or less.
+		/// * If `asset_in` and `asset_out` are the same.
+		/// * If the liquidity pool for the given pair of assets does not exist.
+		/// * If the pool doesn't hold more than `amount_out` of `asset_out` (`InsufficientLiquidity`).
+		/// * If the required input amount is more than `amount_in_max` (`ExcessiveInputAmount`).
+		/// * If the swap operation fails for any reason due to arithmetic error
+		///
+		/// # Events
+		///
+		/// If the function succeeds, it triggers a `Swapped` event with the input and output amounts.
+		///
+		#[pallet::call_index(4)]
+		#[pallet::weight(T::WeightInfo::swap_tokens_for_exact_tokens())]
+		pub fn swap_tokens_for_exact_tokens(
+			origin: OriginFor<T>,
+			asset_in: NativeOrAssetIdOf<T>,
+			asset_out: NativeOrAssetIdOf<T>,
+			amount_out: AssetBalanceOf<T>,
+			amount_in_max: AssetBalanceOf<T>,
+			deadline: Option<BlockNumberFor<T>>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			Self::ensure_not_expired(deadline)?;
+			Self::do_swap_exact_out(&who, asset_in, asset_out, amount_out, amount_in_max)?;
+			Ok(())
+		}
 
-			let (token_a_reserve, token_b_reserve) = if token_a == pool_key.asset_a {
-				(pool.asset_a_balance, pool.asset_b_balance)
-			} else {
-				(pool.asset_b_balance, pool.asset_a_balance)
-			};
-			Ok(Perbill::from_rational(token_a_reserve, token_b_reserve))
+		/// The `swap_along_path` function allows a user to exchange an exact amount of the first
+		/// asset in `path` for the last one, going through the liquidity pool of every consecutive
+		/// pair of assets in between. All hops are executed atomically: if any of them fails or the
+		/// final output is less than `amount_out_min`, none of the swaps take effect.
+		///
+		/// # Arguments
+		///
+		/// * `origin` - The origin caller of this function. This should be signed by the user performing the swap.
+		/// * `path` - The assets to swap through, starting with the input and ending with the output asset.
+		/// * `amount_in` - The exact amount of the first asset in `path` that the user wants to swap.
+		/// * `amount_out_min` - The minimum amount of the last asset in `path` that the user accepts to receive.
+		/// * `deadline` - The last block number at which the call can be executed, if any.
+		///
+		/// # Errors
+		///
+		/// This function will return an error in the following scenarios:
+		///
+		/// * If the origin is not signed (i.e., the function was not called by a user).
+		/// * If the current block is past the `deadline` (`Expired`).
+		/// * If `path` has less than two assets (`InvalidPath`).
+		/// * If any of the hops fails for the same reasons as `swap`.
+		/// * If the final output amount is less than `amount_out_min` (`InsufficientOutputAmount`).
+		///
+		/// # Events
+		///
+		/// If the function succeeds, it triggers a `Swapped` event for every hop and a final `PathSwapped`
+		/// event with the input and the final output amount.
+		///
+		#[pallet::call_index(5)]
+		#[pallet::weight(T::WeightInfo::swap_along_path(path.len() as u32))]
+		pub fn swap_along_path(
+			origin: OriginFor<T>,
+			path: BoundedVec<NativeOrAssetIdOf<T>, T::MaxHops>,
+			amount_in: AssetBalanceOf<T>,
+			amount_out_min: AssetBalanceOf<T>,
+			deadline: Option<BlockNumberFor<T>>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			Self::ensure_not_expired(deadline)?;
+			Self::do_swap_along_path(&who, path, amount_in, amount_out_min)?;
+			Ok(())
 		}
-	}
 
-	impl<T: Config> traits::OraclePrice for Pallet<T> {
-		type AssetId = AssetIdOf<T>;
-		type Balance = AssetBalanceOf<T>;
+		/// The `set_protocol_fee` function allows governance to switch the protocol fee on or off.
+		/// While it's on, every `mint` and `burn` mints `share` of the LP fee growth of the pool
+		/// since the last liquidity event as LP tokens to `recipient`.
+		///
+		/// # Arguments
+		///
+		/// * `origin` - The origin caller of this function. This should be root.
+		/// * `protocol_fee` - The recipient and share of the LP fee growth, or `None` to switch it off.
+		///
+		/// # Errors
+		///
+		/// This function will return an error if the origin is not root.
+		///
+		/// # Events
+		///
+		/// If the function succeeds, it triggers a `ProtocolFeeSet` event.
+		///
+		#[pallet::call_index(6)]
+		#[pallet::weight(T::WeightInfo::set_protocol_fee())]
+		pub fn set_protocol_fee(
+			origin: OriginFor<T>,
+			protocol_fee: Option<(AccountIdOf<T>, Perbill)>,
+		) -> DispatchResult {
+			ensure_root(origin)?;
+			<ProtocolFee<T>>::set(protocol_fee.clone());
+			Self::deposit_event(Event::ProtocolFeeSet { protocol_fee });
+			Ok(())
+		}
 
-		fn get_price_for(
-			asset_in: Self::AssetId,
-			amount_in: Self::Balance,
-			asset_out: Self::AssetId,
-		) -> Result<Self::Balance, DispatchError> {
-			Self::ensure_assets_exist(asset_in, asset_out)?;
-			ensure!(asset_in != asset_out, Error::<T>::IdenticalAssets);
+		/// The `create_pool` function allows a user to create an empty liquidity pool for two
+		/// assets, to which liquidity can then be added with `mint`. A new LP token is created for
+		/// the pool and `PoolDeposit` of the native currency is held from the caller until the pool
+		/// is destroyed. The caller also pays the existential deposit of the account of the pool,
+		/// which stays in it for good.
+		///
+		/// # Arguments
+		///
+		/// * `origin` - The origin caller of this function. This should be signed by the user creating the pool.
+		/// * `asset_a` - The identifier for the first type of asset of the pool.
+		/// * `asset_b` - The identifier for the second type of asset of the pool.
+		/// * `fee_tier` - The swap fee of the pool, one of `SwapFeeTiers`.
+		///
+		/// # Errors
+		///
+		/// This function will return an error in the following scenarios:
+		///
+		/// * If the origin is not signed (i.e., the function was not called by a user).
+		/// * If the provided assets do not exist.
+		/// * If `asset_a` and `asset_b` are the same.
+		/// * If `fee_tier` is not allowed (`UnsupportedFeeTier`).
+		/// * If there is already a liquidity pool for the given asset pair (`LiquidityPoolExists`).
+		/// * If the caller can't afford the `PoolDeposit` and the existential deposit of the pool
+		///   account.
+		/// * If no asset id is left in the range reserved for LP tokens, or the next
+		///   `MaxLpAssetIdProbes` ids are all taken by other assets (`AssetLimitReached`).
+		///
+		/// # Events
+		///
+		/// If the function succeeds, it triggers a `LiquidityPoolCreated` event with the LP token asset id.
+		///
+		#[pallet::call_index(7)]
+		#[pallet::weight(T::WeightInfo::create_pool().saturating_add(
+			T::DbWeight::get().reads(T::MaxLpAssetIdProbes::get().saturating_sub(1).into())
+		))]
+		pub fn create_pool(
+			origin: OriginFor<T>,
+			asset_a: NativeOrAssetIdOf<T>,
+			asset_b: NativeOrAssetIdOf<T>,
+			fee_tier: Permill,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			Self::do_create_pool(&who, asset_a, asset_b, fee_tier, None)
+		}
 
-			let pool_key = AssetPair::new(asset_in.clone(), asset_out.clone());
-			let pool = <LiquidityPools<T>>::get(pool_key.clone())
-				.ok_or_else(|| DispatchError::from(Error::<T>::LiquidityPoolDoesNotExist))?;
+		/// The `flash_swap` function allows a user to receive assets from a liquidity pool before
+		/// paying for them, like the flash swaps of Uniswap V2. The requested amounts are sent to
+		/// the user first, then `FlashSwapCallback` is called to pay the pool back in either
+		/// asset. The payment has to keep the constant product of the pool including the swap fee,
+		/// otherwise the whole flash swap is reverted. Like in Uniswap V2, assets sent to the pool
+		/// account before the flash swap count towards the payment as well.
+		///
+		/// # Arguments
+		///
+		/// * `origin` - The origin caller of this function. This should be signed by the user borrowing from the pool.
+		/// * `asset_a` - The identifier for the first type of asset of the pool.
+		/// * `asset_b` - The identifier for the second type of asset of the pool.
+		/// * `amount_a_out` - The amount of `asset_a` that the user wants to receive.
+		/// * `amount_b_out` - The amount of `asset_b` that the user wants to receive.
+		/// * `deadline` - The last block number at which the call can be executed, if any.
+		///
+		/// # Errors
+		///
+		/// This function will return an error in the following scenarios:
+		///
+		/// * If the origin is not signed (i.e., the function was not called by a user).
+		/// * If the current block is past the `deadline` (`Expired`).
+		/// * If the provided assets do not exist.
+		/// * If `asset_a` and `asset_b` are the same.
+		/// * If the liquidity pool for the given asset pair does not exist.
+		/// * If both output amounts are 0 (`InsufficientOutputAmount`).
+		/// * If an output amount isn't below the reserve of the pool (`InsufficientLiquidity`).
+		/// * If the pool keeps its assets in the shared pallet account (`SharedPoolAccount`).
+		/// * If the callback fails or doesn't pay anything back (`InsufficientInputAmount`).
+		/// * If the payment doesn't keep the constant product of the pool (`InvariantViolated`).
+		///
+		/// # Events
+		///
+		/// If the function succeeds, it triggers a `FlashSwapped` event.
+		///
+		#[pallet::call_index(8)]
+		#[pallet::weight(T::WeightInfo::flash_swap().saturating_add(T::FlashSwapCallback::weight()))]
+		pub fn flash_swap(
+			origin: OriginFor<T>,
+			asset_a: NativeOrAssetIdOf<T>,
+			asset_b: NativeOrAssetIdOf<T>,
+			amount_a_out: AssetBalanceOf<T>,
+			amount_b_out: AssetBalanceOf<T>,
+			deadline: Option<BlockNumberFor<T>>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			Self::ensure_not_expired(deadline)?;
+			Self::do_flash_swap(&who, asset_a, asset_b, amount_a_out, amount_b_out)
+		}
 
-			let (reserve_in, reserve_out) = if asset_in == pool_key.asset_a {
-				(pool.asset_a_balance, pool.asset_b_balance)
+		/// The `sync` function allows anyone to set the reserves of a liquidity pool to the
+		/// balances of its account, like `sync` of Uniswap V2. Assets transferred straight to the
+		/// pool account are added to the pool this way, to the benefit of its liquidity providers.
+		///
+		/// # Arguments
+		///
+		/// * `origin` - The origin caller of this function. This should be signed by the user syncing the pool.
+		/// * `asset_a` - The identifier for the first type of asset of the pool.
+		/// * `asset_b` - The identifier for the second type of asset of the pool.
+		///
+		/// # Errors
+		///
+		/// This function will return an error in the following scenarios:
+		///
+		/// * If the origin is not signed (i.e., the function was not called by a user).
+		/// * If the provided assets do not exist.
+		/// * If `asset_a` and `asset_b` are the same.
+		/// * If the liquidity pool for the given asset pair does not exist.
+		/// * If the pool has no liquidity yet (`InsufficientLiquidity`).
+		/// * If the pool keeps its assets in the shared pallet account (`SharedPoolAccount`).
+		///
+		/// # Events
+		///
+		/// If the function succeeds, it triggers a `Synced` event with the new reserves.
+		///
+		#[pallet::call_index(9)]
+		#[pallet::weight(T::WeightInfo::sync())]
+		pub fn sync(
+			origin: OriginFor<T>,
+			asset_a: NativeOrAssetIdOf<T>,
+			asset_b: NativeOrAssetIdOf<T>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			Self::ensure_assets_exist(asset_a, asset_b)?;
+			ensure!(asset_a != asset_b, Error::<T>::IdenticalAssets);
+			let (pool_asset_pair, mut pool) = Self::get_pool(asset_a, asset_b)
+				.ok_or_else(|| DispatchError::from(Error::<T>::LiquidityPoolDoesNotExist))?;
+
+			pool.sync(&pool_asset_pair)?;
+			<LiquidityPools<T>>::set(&pool_asset_pair, Some(pool.clone()));
+
+			Self::deposit_event(Event::Synced {
+				who,
+				pool_id: pool.id,
+				asset_a: pool_asset_pair.asset_a,
+				asset_b: pool_asset_pair.asset_b,
+				reserve_a: pool.asset_a_balance,
+				reserve_b: pool.asset_b_balance,
+			});
+			Ok(())
+		}
+
+		/// The `skim` function allows anyone to send the balances of a liquidity pool account
+		/// above the reserves of the pool to `to`, like `skim` of Uniswap V2. It takes out assets
+		/// that were transferred straight to the pool account without changing the pool.
+		///
+		/// # Arguments
+		///
+		/// * `origin` - The origin caller of this function. This should be signed by the user skimming the pool.
+		/// * `asset_a` - The identifier for the first type of asset of the pool.
+		/// * `asset_b` - The identifier for the second type of asset of the pool.
+		/// * `to` - The account receiving the excess balances.
+		///
+		/// # Errors
+		///
+		/// This function will return an error in the following scenarios:
+		///
+		/// * If the origin is not signed (i.e., the function was not called by a user).
+		/// * If the provided assets do not exist.
+		/// * If `asset_a` and `asset_b` are the same.
+		/// * If the liquidity pool for the given asset pair does not exist.
+		/// * If the pool keeps its assets in the shared pallet account (`SharedPoolAccount`).
+		/// * If the transfer of the excess balances to `to` fails.
+		///
+		/// # Events
+		///
+		/// If the function succeeds, it triggers a `Skimmed` event with the amounts sent.
+		///
+		#[pallet::call_index(10)]
+		#[pallet::weight(T::WeightInfo::skim())]
+		pub fn skim(
+			origin: OriginFor<T>,
+			asset_a: NativeOrAssetIdOf<T>,
+			asset_b: NativeOrAssetIdOf<T>,
+			to: AccountIdOf<T>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			Self::ensure_assets_exist(asset_a, asset_b)?;
+			ensure!(asset_a != asset_b, Error::<T>::IdenticalAssets);
+			let (pool_asset_pair, pool) = Self::get_pool(asset_a, asset_b)
+				.ok_or_else(|| DispatchError::from(Error::<T>::LiquidityPoolDoesNotExist))?;
+
+			let (amount_a, amount_b) = pool.skim(&pool_asset_pair, &to)?;
+
+			Self::deposit_event(Event::Skimmed {
+				who,
+				to,
+				pool_id: pool.id,
+				asset_a: pool_asset_pair.asset_a,
+				asset_b: pool_asset_pair.asset_b,
+				amount_a,
+				amount_b,
+			});
+			Ok(())
+		}
+
+		/// The `pause_pool` function allows the admin origin to pause a liquidity pool, for
+		/// example when one of its assets was exploited. Swaps, flash swaps and `mint` are
+		/// rejected in a paused pool, while `burn` keeps working so that liquidity providers can
+		/// always exit.
+		///
+		/// # Arguments
+		///
+		/// * `origin` - The origin caller of this function. This should be `AdminOrigin`.
+		/// * `asset_a` - The identifier for the first type of asset of the pool.
+		/// * `asset_b` - The identifier for the second type of asset of the pool.
+		///
+		/// # Errors
+		///
+		/// This function will return an error in the following scenarios:
+		///
+		/// * If the origin is not `AdminOrigin`.
+		/// * If the provided assets do not exist.
+		/// * If `asset_a` and `asset_b` are the same.
+		/// * If the liquidity pool for the given asset pair does not exist.
+		/// * If the pool is already paused (`PoolPaused`).
+		///
+		/// # Events
+		///
+		/// If the function succeeds, it triggers a `PoolPaused` event.
+		///
+		#[pallet::call_index(11)]
+		#[pallet::weight(T::WeightInfo::pause_pool())]
+		pub fn pause_pool(
+			origin: OriginFor<T>,
+			asset_a: NativeOrAssetIdOf<T>,
+			asset_b: NativeOrAssetIdOf<T>,
+		) -> DispatchResult {
+			T::AdminOrigin::ensure_origin(origin)?;
+			Self::ensure_assets_exist(asset_a, asset_b)?;
+			ensure!(asset_a != asset_b, Error::<T>::IdenticalAssets);
+			let (pool_asset_pair, pool) = Self::get_pool(asset_a, asset_b)
+				.ok_or_else(|| DispatchError::from(Error::<T>::LiquidityPoolDoesNotExist))?;
+			ensure!(!<PausedPools<T>>::get(&pool_asset_pair), Error::<T>::PoolPaused);
+
+			<PausedPools<T>>::insert(&pool_asset_pair, true);
+
+			Self::deposit_event(Event::PoolPaused {
+				pool_id: pool.id,
+				asset_a: pool_asset_pair.asset_a,
+				asset_b: pool_asset_pair.asset_b,
+			});
+			Ok(())
+		}
+
+		/// The `unpause_pool` function allows the admin origin to unpause a liquidity pool that
+		/// was paused with `pause_pool`.
+		///
+		/// # Arguments
+		///
+		/// * `origin` - The origin caller of this function. This should be `AdminOrigin`.
+		/// * `asset_a` - The identifier for the first type of asset of the pool.
+		/// * `asset_b` - The identifier for the second type of asset of the pool.
+		///
+		/// # Errors
+		///
+		/// This function will return an error in the following scenarios:
+		///
+		/// * If the origin is not `AdminOrigin`.
+		/// * If the provided assets do not exist.
+		/// * If `asset_a` and `asset_b` are the same.
+		/// * If the liquidity pool for the given asset pair does not exist.
+		/// * If the pool isn't paused (`PoolNotPaused`).
+		///
+		/// # Events
+		///
+		/// If the function succeeds, it triggers a `PoolUnpaused` event.
+		///
+		#[pallet::call_index(12)]
+		#[pallet::weight(T::WeightInfo::unpause_pool())]
+		pub fn unpause_pool(
+			origin: OriginFor<T>,
+			asset_a: NativeOrAssetIdOf<T>,
+			asset_b: NativeOrAssetIdOf<T>,
+		) -> DispatchResult {
+			T::AdminOrigin::ensure_origin(origin)?;
+			Self::ensure_assets_exist(asset_a, asset_b)?;
+			ensure!(asset_a != asset_b, Error::<T>::IdenticalAssets);
+			let (pool_asset_pair, pool) = Self::get_pool(asset_a, asset_b)
+				.ok_or_else(|| DispatchError::from(Error::<T>::LiquidityPoolDoesNotExist))?;
+			ensure!(<PausedPools<T>>::get(&pool_asset_pair), Error::<T>::PoolNotPaused);
+
+			<PausedPools<T>>::remove(&pool_asset_pair);
+
+			Self::deposit_event(Event::PoolUnpaused {
+				pool_id: pool.id,
+				asset_a: pool_asset_pair.asset_a,
+				asset_b: pool_asset_pair.asset_b,
+			});
+			Ok(())
+		}
+
+		/// The `set_trading_enabled` function allows the admin origin to switch trading off in all
+		/// liquidity pools at once, and back on. While it's off, every pool behaves like a paused
+		/// one: only `burn` works.
+		///
+		/// # Arguments
+		///
+		/// * `origin` - The origin caller of this function. This should be `AdminOrigin`.
+		/// * `enabled` - Whether the pools can be traded in and added to.
+		///
+		/// # Errors
+		///
+		/// This function will return an error if the origin is not `AdminOrigin`.
+		///
+		/// # Events
+		///
+		/// If the function succeeds, it triggers a `TradingEnabledSet` event.
+		///
+		#[pallet::call_index(13)]
+		#[pallet::weight(T::WeightInfo::set_trading_enabled())]
+		pub fn set_trading_enabled(origin: OriginFor<T>, enabled: bool) -> DispatchResult {
+			T::AdminOrigin::ensure_origin(origin)?;
+			<TradingEnabled<T>>::put(enabled);
+			Self::deposit_event(Event::TradingEnabledSet { enabled });
+			Ok(())
+		}
+
+		/// The `destroy_pool` function allows the creator of a liquidity pool that never had
+		/// liquidity to destroy it and get the `PoolDeposit` back. Pools that had liquidity are
+		/// destroyed by `burn` when the last of it is removed instead. The existential deposit
+		/// paid for the pool account stays in it.
+		///
+		/// # Arguments
+		///
+		/// * `origin` - The origin caller of this function. This should be signed by the creator of the pool.
+		/// * `asset_a` - The identifier for the first type of asset of the pool.
+		/// * `asset_b` - The identifier for the second type of asset of the pool.
+		///
+		/// # Errors
+		///
+		/// This function will return an error in the following scenarios:
+		///
+		/// * If the origin is not signed (i.e., the function was not called by a user).
+		/// * If the provided assets do not exist.
+		/// * If `asset_a` and `asset_b` are the same.
+		/// * If the liquidity pool for the given asset pair does not exist.
+		/// * If the caller didn't create the pool (`NotPoolDepositor`).
+		/// * If any LP tokens of the pool were minted (`PoolNotEmpty`).
+		///
+		/// # Events
+		///
+		/// If the function succeeds, it triggers a `LiquidityPoolDestroyed` event.
+		///
+		#[pallet::call_index(14)]
+		#[pallet::weight(T::WeightInfo::destroy_pool())]
+		pub fn destroy_pool(
+			origin: OriginFor<T>,
+			asset_a: NativeOrAssetIdOf<T>,
+			asset_b: NativeOrAssetIdOf<T>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			Self::ensure_assets_exist(asset_a, asset_b)?;
+			ensure!(asset_a != asset_b, Error::<T>::IdenticalAssets);
+			let (pool_asset_pair, pool) = Self::get_pool(asset_a, asset_b)
+				.ok_or_else(|| DispatchError::from(Error::<T>::LiquidityPoolDoesNotExist))?;
+			ensure!(pool.depositor == who, Error::<T>::NotPoolDepositor);
+			ensure!(T::Fungibles::total_issuance(pool.id).is_zero(), Error::<T>::PoolNotEmpty);
+
+			Self::remove_pool(&pool_asset_pair, &pool)?;
+
+			Self::deposit_event(Event::LiquidityPoolDestroyed {
+				who,
+				pool_id: pool.id,
+				asset_a: pool_asset_pair.asset_a,
+				asset_b: pool_asset_pair.asset_b,
+			});
+			Ok(())
+		}
+	}
+
+	impl<T: Config> Pallet<T> {
+		/// Removes the liquidity pool of `pool_asset_pair` from storage and releases the creation
+		/// deposit of `pool` to its depositor.
+		fn remove_pool(pool_asset_pair: &AssetPair<T>, pool: &LiquidityPool<T>) -> DispatchResult {
+			<LiquidityPools<T>>::remove(pool_asset_pair);
+			<PriceObservations<T>>::remove(pool_asset_pair);
+			<PausedPools<T>>::remove(pool_asset_pair);
+			// Pools migrated from before creation deposits have none to release
+			if !pool.deposit.is_zero() {
+				T::NativeBalance::release(
+					&HoldReason::PoolDeposit.into(),
+					&pool.depositor,
+					pool.deposit,
+					Precision::BestEffort,
+				)?;
+			}
+			Ok(())
+		}
+
+		/// Creates the liquidity pool of `asset_a` and `asset_b` with `fee_tier`, holding the
+		/// `PoolDeposit` from `who`, who also pays the existential deposit of the pool account. Its
+		/// LP token is `lp_asset_id`, or the next free id of the range reserved for LP tokens if
+		/// none is given.
+		pub(crate) fn do_create_pool(
+			who: &AccountIdOf<T>,
+			asset_a: NativeOrAssetIdOf<T>,
+			asset_b: NativeOrAssetIdOf<T>,
+			fee_tier: Permill,
+			lp_asset_id: Option<AssetIdOf<T>>,
+		) -> DispatchResult {
+			Self::ensure_assets_exist(asset_a, asset_b)?;
+			ensure!(asset_a != asset_b, Error::<T>::IdenticalAssets);
+			ensure!(T::SwapFeeTiers::get().contains(&fee_tier), Error::<T>::UnsupportedFeeTier);
+
+			let pool_asset_pair = AssetPair::new(asset_a, asset_b);
+			ensure!(
+				!LiquidityPools::<T>::contains_key(&pool_asset_pair),
+				Error::<T>::LiquidityPoolExists
+			);
+
+			let deposit = T::PoolDeposit::get();
+			T::NativeBalance::hold(&HoldReason::PoolDeposit.into(), who, deposit)?;
+
+			// Create the LP token with the next free asset id of the reserved range, unless one
+			// was given. A given id moves the next free one past it, so that later pools don't
+			// have to probe it
+			let lp_asset_id = match lp_asset_id {
+				Some(lp_asset_id) => {
+					if NextLpAssetId::<T>::get().map_or(true, |next| next <= lp_asset_id) {
+						NextLpAssetId::<T>::put(lp_asset_id.saturating_add(1));
+					}
+					lp_asset_id
+				},
+				None => Self::next_lp_asset_id()?,
+			};
+			let pallet_id: T::AccountId = T::PalletId::get().into_account_truncating();
+			T::Fungibles::create(lp_asset_id.clone(), pallet_id, true, AssetBalanceOf::<T>::one())?;
+
+			// Each pool holds its assets in its own account. Its creator pays the existential
+			// deposit, which keeps it alive for the assets that aren't sufficient and for the
+			// minimum liquidity locked in it forever
+			let manager = Self::pool_account(lp_asset_id);
+			NativeOrAssetAdapter::<T>::transfer(
+				NativeOrAssetId::Native,
+				who,
+				&manager,
+				NativeOrAssetAdapter::<T>::minimum_balance(NativeOrAssetId::Native),
+				Preservation::Preserve,
+			)?;
+
+			let pool = LiquidityPool {
+				id: lp_asset_id.clone(),
+				manager,
+				asset_a_balance: AssetBalanceOf::<T>::zero(),
+				asset_b_balance: AssetBalanceOf::<T>::zero(),
+				fee: fee_tier,
+				k_last: AssetBalanceOf::<T>::zero(),
+				depositor: who.clone(),
+				deposit,
+				price_a_cumulative_last: FixedU128::zero(),
+				price_b_cumulative_last: FixedU128::zero(),
+				block_timestamp_last: Self::current_timestamp(),
+			};
+			<LiquidityPools<T>>::insert(&pool_asset_pair, pool);
+
+			Self::deposit_event(Event::LiquidityPoolCreated {
+				creator: who.clone(),
+				pool_id: lp_asset_id,
+				asset_a: pool_asset_pair.asset_a,
+				asset_b: pool_asset_pair.asset_b,
+				fee: fee_tier,
+			});
+			Ok(())
+		}
+
+		/// Adds liquidity from `who` to the pool of `asset_a` and `asset_b`, taking at most the
+		/// desired amounts in the ratio of the pool.
+		pub(crate) fn do_mint(
+			who: &AccountIdOf<T>,
+			asset_a: NativeOrAssetIdOf<T>,
+			asset_b: NativeOrAssetIdOf<T>,
+			amount_a_desired: AssetBalanceOf<T>,
+			amount_b_desired: AssetBalanceOf<T>,
+			amount_a_min: AssetBalanceOf<T>,
+			amount_b_min: AssetBalanceOf<T>,
+		) -> DispatchResult {
+			Self::ensure_assets_exist(asset_a, asset_b)?;
+			// Assets should be different to create a pool
+			ensure!(asset_a != asset_b, Error::<T>::IdenticalAssets);
+			ensure!(
+				amount_a_desired > AssetBalanceOf::<T>::zero() &&
+					amount_b_desired > AssetBalanceOf::<T>::zero(),
+				Error::<T>::InsufficientInputAmount
+			);
+
+			let pool_asset_pair = AssetPair::new(asset_a.clone(), asset_b.clone());
+			let mut pool = LiquidityPools::<T>::get(pool_asset_pair.clone())
+				.ok_or_else(|| DispatchError::from(Error::<T>::LiquidityPoolDoesNotExist))?;
+
+			// Add liquidity
+			let (amount_a, amount_b, lp_minted) = pool.add_liquidity(
+				&pool_asset_pair,
+				amount_a_desired,
+				amount_b_desired,
+				amount_a_min,
+				amount_b_min,
+				who,
+			)?;
+			// Set or update the existing storage with new balances
+			<LiquidityPools<T>>::set(&pool_asset_pair, Some(pool.clone()));
+
+			Self::deposit_event(Event::LiquidityAdded {
+				who: who.clone(),
+				pool_id: pool.id,
+				asset_a: pool_asset_pair.asset_a,
+				asset_b: pool_asset_pair.asset_b,
+				amount_a,
+				amount_b,
+				lp_minted,
+				reserve_a: pool.asset_a_balance,
+				reserve_b: pool.asset_b_balance,
+			});
+
+			Ok(())
+		}
+
+		/// Swaps an exact `amount_in` of `asset_in` for `asset_out` in their liquidity pool and
+		/// returns the amount of `asset_out` sent to `who`.
+		pub(crate) fn do_swap_exact_in(
+			who: &AccountIdOf<T>,
+			asset_in: NativeOrAssetIdOf<T>,
+			asset_out: NativeOrAssetIdOf<T>,
+			amount_in: AssetBalanceOf<T>,
+			amount_out_min: AssetBalanceOf<T>,
+		) -> Result<AssetBalanceOf<T>, DispatchError> {
+			Self::ensure_assets_exist(asset_in, asset_out)?;
+			ensure!(asset_in != asset_out, Error::<T>::IdenticalAssets);
+			let pool_asset_pair = AssetPair::new(asset_in.clone(), asset_out.clone());
+			let mut pool = LiquidityPools::<T>::get(pool_asset_pair.clone())
+				.ok_or_else(|| DispatchError::from(Error::<T>::LiquidityPoolDoesNotExist))?;
+
+			// Swapping for asset_in (asset_out) in the pool with amount_in of asset_in
+			let amount_out = pool.swap(
+				who,
+				pool_asset_pair.clone(),
+				asset_in.clone(),
+				asset_out.clone(),
+				amount_in,
+				amount_out_min,
+			)?;
+			// Update the existing storage with new balances
+			<LiquidityPools<T>>::set(&pool_asset_pair, Some(pool.clone()));
+
+			let (reserve_in, reserve_out) = pool.reserves_for(&pool_asset_pair, asset_out);
+			Self::deposit_event(Event::Swapped {
+				who: who.clone(),
+				pool_id: pool.id,
+				asset_in,
+				asset_out,
+				amount_in,
+				amount_out,
+				reserve_in,
+				reserve_out,
+			});
+
+			Ok(amount_out)
+		}
+
+		/// Swaps `asset_in` for an exact `amount_out` of `asset_out` in their liquidity pool and
+		/// returns the amount of `asset_in` taken from `who`.
+		pub(crate) fn do_swap_exact_out(
+			who: &AccountIdOf<T>,
+			asset_in: NativeOrAssetIdOf<T>,
+			asset_out: NativeOrAssetIdOf<T>,
+			amount_out: AssetBalanceOf<T>,
+			amount_in_max: AssetBalanceOf<T>,
+		) -> Result<AssetBalanceOf<T>, DispatchError> {
+			Self::ensure_assets_exist(asset_in, asset_out)?;
+			ensure!(asset_in != asset_out, Error::<T>::IdenticalAssets);
+			let pool_asset_pair = AssetPair::new(asset_in.clone(), asset_out.clone());
+			let mut pool = LiquidityPools::<T>::get(pool_asset_pair.clone())
+				.ok_or_else(|| DispatchError::from(Error::<T>::LiquidityPoolDoesNotExist))?;
+
+			let amount_in = pool.swap_exact_out(
+				who,
+				pool_asset_pair.clone(),
+				asset_in.clone(),
+				asset_out.clone(),
+				amount_out,
+				amount_in_max,
+			)?;
+			// Update the existing storage with new balances
+			<LiquidityPools<T>>::set(&pool_asset_pair, Some(pool.clone()));
+
+			let (reserve_in, reserve_out) = pool.reserves_for(&pool_asset_pair, asset_out);
+			Self::deposit_event(Event::Swapped {
+				who: who.clone(),
+				pool_id: pool.id,
+				asset_in,
+				asset_out,
+				amount_in,
+				amount_out,
+				reserve_in,
+				reserve_out,
+			});
+
+			Ok(amount_in)
+		}
+
+		/// Swaps an exact `amount_in` of the first asset in `path` for the last one, hopping through
+		/// every pool along the way, and returns the amount of the last asset sent to `who`.
+		pub(crate) fn do_swap_along_path(
+			who: &AccountIdOf<T>,
+			path: BoundedVec<NativeOrAssetIdOf<T>, T::MaxHops>,
+			amount_in: AssetBalanceOf<T>,
+			amount_out_min: AssetBalanceOf<T>,
+		) -> Result<AssetBalanceOf<T>, DispatchError> {
+			ensure!(path.len() >= 2, Error::<T>::InvalidPath);
+
+			// Roll back every hop if a later one fails or the final output is too low
+			with_storage_layer(|| {
+				let mut amount_out = amount_in;
+				for hop in path.windows(2) {
+					amount_out = Self::do_swap_exact_in(
+						who,
+						hop[0],
+						hop[1],
+						amount_out,
+						AssetBalanceOf::<T>::zero(),
+					)?;
+				}
+				ensure!(amount_out >= amount_out_min, Error::<T>::InsufficientOutputAmount);
+
+				Self::deposit_event(Event::PathSwapped {
+					who: who.clone(),
+					path,
+					amount_in,
+					amount_out,
+				});
+
+				Ok(amount_out)
+			})
+		}
+
+		/// Sends `amount_a_out` of `asset_a` and `amount_b_out` of `asset_b` to `who` before they
+		/// are paid for by `FlashSwapCallback`, reverting everything if the pool isn't paid back
+		/// enough.
+		pub fn do_flash_swap(
+			who: &AccountIdOf<T>,
+			asset_a: NativeOrAssetIdOf<T>,
+			asset_b: NativeOrAssetIdOf<T>,
+			amount_a_out: AssetBalanceOf<T>,
+			amount_b_out: AssetBalanceOf<T>,
+		) -> DispatchResult {
+			Self::ensure_assets_exist(asset_a, asset_b)?;
+			ensure!(asset_a != asset_b, Error::<T>::IdenticalAssets);
+
+			// Roll back the optimistic transfers and whatever the callback did on failure
+			with_storage_layer(|| {
+				let pool_asset_pair = AssetPair::new(asset_a, asset_b);
+				let mut pool = LiquidityPools::<T>::get(&pool_asset_pair)
+					.ok_or_else(|| DispatchError::from(Error::<T>::LiquidityPoolDoesNotExist))?;
+
+				// Amounts in the order of the pool assets
+				let (amount_a_out, amount_b_out) = if asset_a == pool_asset_pair.asset_a {
+					(amount_a_out, amount_b_out)
+				} else {
+					(amount_b_out, amount_a_out)
+				};
+				let (amount_a_in, amount_b_in) =
+					pool.flash_swap(who, &pool_asset_pair, amount_a_out, amount_b_out)?;
+				<LiquidityPools<T>>::set(&pool_asset_pair, Some(pool.clone()));
+
+				Self::deposit_event(Event::FlashSwapped {
+					who: who.clone(),
+					pool_id: pool.id,
+					asset_a: pool_asset_pair.asset_a,
+					asset_b: pool_asset_pair.asset_b,
+					amount_a_out,
+					amount_b_out,
+					amount_a_in,
+					amount_b_in,
+					reserve_a: pool.asset_a_balance,
+					reserve_b: pool.asset_b_balance,
+				});
+
+				Ok(())
+			})
+		}
+
+		/// Walks every liquidity pool reachable from the last asset in `path` and keeps the route
+		/// to `asset_out` with the highest output amount in `best`.
+		fn search_routes(
+			pools: &[(AssetPair<T>, LiquidityPool<T>)],
+			path: &mut Vec<NativeOrAssetIdOf<T>>,
+			amount_in: AssetBalanceOf<T>,
+			asset_out: NativeOrAssetIdOf<T>,
+			hops_left: u32,
+			best: &mut Option<(Vec<NativeOrAssetIdOf<T>>, AssetBalanceOf<T>)>,
+		) {
+			let asset_in = match path.last() {
+				Some(asset) if hops_left > 0 => *asset,
+				_ => return,
+			};
+
+			for (pool_key, pool) in pools.iter() {
+				let (next_asset, reserve_in, reserve_out) = if pool_key.asset_a == asset_in {
+					(pool_key.asset_b, pool.asset_a_balance, pool.asset_b_balance)
+				} else if pool_key.asset_b == asset_in {
+					(pool_key.asset_a, pool.asset_b_balance, pool.asset_a_balance)
+				} else {
+					continue
+				};
+				// Never go through the same asset twice and skip pools that can't take the swap
+				if path.contains(&next_asset) || reserve_in <= amount_in {
+					continue
+				}
+				let quote = pool.calculate_output_for(amount_in, reserve_in, reserve_out);
+				let amount_out = match quote {
+					Ok(amount_out) if !amount_out.is_zero() => amount_out,
+					_ => continue,
+				};
+
+				path.push(next_asset);
+				if next_asset == asset_out {
+					if best.as_ref().map_or(true, |(_, best_out)| amount_out > *best_out) {
+						*best = Some((path.clone(), amount_out));
+					}
+				} else {
+					Self::search_routes(pools, path, amount_out, asset_out, hops_left - 1, best);
+				}
+				path.pop();
+			}
+		}
+
+		/// Finds the path of at most `max_hops` pools from `asset_in` to `asset_out` that gives the
+		/// highest output for `amount_in`, along with that output amount.
+		///
+		/// It reads every liquidity pool, so it is meant for the `DexApi` runtime API and must not
+		/// be called from dispatchables, whose weight could not cover it. Other pallets can use
+		/// `OraclePrice::find_best_route`, which reads at most `MaxPoolsScanned` pools.
+		pub fn find_best_route(
+			asset_in: NativeOrAssetIdOf<T>,
+			amount_in: AssetBalanceOf<T>,
+			asset_out: NativeOrAssetIdOf<T>,
+			max_hops: u32,
+		) -> Result<(Vec<NativeOrAssetIdOf<T>>, AssetBalanceOf<T>), DispatchError> {
+			Self::do_find_best_route(asset_in, amount_in, asset_out, max_hops, usize::MAX)
+		}
+
+		/// Finds the best route like `find_best_route` among the first `max_pools` liquidity
+		/// pools.
+		fn do_find_best_route(
+			asset_in: NativeOrAssetIdOf<T>,
+			amount_in: AssetBalanceOf<T>,
+			asset_out: NativeOrAssetIdOf<T>,
+			max_hops: u32,
+			max_pools: usize,
+		) -> Result<(Vec<NativeOrAssetIdOf<T>>, AssetBalanceOf<T>), DispatchError> {
+			Self::ensure_assets_exist(asset_in, asset_out)?;
+			ensure!(asset_in != asset_out, Error::<T>::IdenticalAssets);
+			ensure!(<TradingEnabled<T>>::get(), Error::<T>::TradingDisabled);
+
+			// Longer routes could not be executed with `swap_along_path`
+			let max_hops = max_hops.min(T::MaxHops::get().saturating_sub(1));
+			// Nor could routes through paused pools
+			let pools: Vec<_> = <LiquidityPools<T>>::iter()
+				.take(max_pools)
+				.filter(|(pool_key, _)| !<PausedPools<T>>::get(pool_key))
+				.collect();
+			let mut path = Vec::new();
+			path.push(asset_in);
+			let mut best = None;
+			Self::search_routes(&pools, &mut path, amount_in, asset_out, max_hops, &mut best);
+
+			best.ok_or_else(|| DispatchError::from(Error::<T>::NoRouteFound))
+		}
+
+		/// Returns the reserves of the liquidity pool of `asset_a` and `asset_b`, in the order the
+		/// assets were given.
+		pub fn get_reserves(
+			asset_a: NativeOrAssetIdOf<T>,
+			asset_b: NativeOrAssetIdOf<T>,
+		) -> Result<(AssetBalanceOf<T>, AssetBalanceOf<T>), DispatchError> {
+			Self::ensure_assets_exist(asset_a, asset_b)?;
+			ensure!(asset_a != asset_b, Error::<T>::IdenticalAssets);
+			let (pool_key, pool) = Self::get_pool(asset_a, asset_b)
+				.ok_or_else(|| DispatchError::from(Error::<T>::LiquidityPoolDoesNotExist))?;
+
+			if asset_a == pool_key.asset_a {
+				Ok((pool.asset_a_balance, pool.asset_b_balance))
+			} else {
+				Ok((pool.asset_b_balance, pool.asset_a_balance))
+			}
+		}
+
+		/// Returns the liquidity pool of `asset_a` and `asset_b` along with its ordered key.
+		pub fn get_pool(
+			asset_a: NativeOrAssetIdOf<T>,
+			asset_b: NativeOrAssetIdOf<T>,
+		) -> Option<(AssetPair<T>, LiquidityPool<T>)> {
+			let pool_key = AssetPair::new(asset_a, asset_b);
+			<LiquidityPools<T>>::get(pool_key.clone()).map(|pool| (pool_key, pool))
+		}
+
+		/// Checks that the bookkeeping of every liquidity pool is backed by the balances of its
+		/// manager and by its LP token.
+		#[cfg(any(feature = "try-runtime", test))]
+		pub(crate) fn do_try_state() -> Result<(), sp_runtime::TryRuntimeError> {
+			use sp_runtime::traits::CheckedAdd;
+			use sp_std::collections::btree_map::BTreeMap;
+
+			ensure!(!<FlashSwapLock<T>>::get(), "A flash swap was left in progress");
+
+			// Pools created before per pool accounts share their manager account, so the reserves
+			// of an asset are checked together for each manager. The manager can hold more than
+			// the reserves as anyone can transfer to it.
+			let mut reserves = BTreeMap::<_, AssetBalanceOf<T>>::new();
+			for (pool_key, pool) in <LiquidityPools<T>>::iter() {
+				ensure!(
+					T::Fungibles::asset_exists(pool.id),
+					"The LP token of a pool doesn't exist"
+				);
+				let lp_issuance = T::Fungibles::total_issuance(pool.id);
+				if lp_issuance.is_zero() {
+					ensure!(
+						pool.asset_a_balance.is_zero() && pool.asset_b_balance.is_zero(),
+						"A pool without LP tokens has reserves"
+					);
+				} else {
+					ensure!(
+						!pool.asset_a_balance.is_zero() && !pool.asset_b_balance.is_zero(),
+						"A pool with LP tokens has no reserves"
+					);
+					ensure!(
+						lp_issuance >= T::MinimumLiquidity::get().into(),
+						"The LP token issuance of a pool is below the minimum liquidity"
+					);
+				}
+
+				for (asset, reserve) in [
+					(pool_key.asset_a, pool.asset_a_balance),
+					(pool_key.asset_b, pool.asset_b_balance),
+				] {
+					let total =
+						reserves.entry((pool.manager.clone(), asset)).or_insert_with(Zero::zero);
+					*total =
+						total.checked_add(&reserve).ok_or("The reserves of an asset overflow")?;
+				}
+			}
+
+			for ((manager, asset), reserve) in reserves {
+				ensure!(
+					NativeOrAssetAdapter::<T>::balance(asset, &manager) >= reserve,
+					"The reserves of the pools exceed the balance of their manager"
+				);
+			}
+			Ok(())
+		}
+	}
+
+	impl<T: Config> traits::TokenRatio for Pallet<T> {
+		type AssetId = NativeOrAssetIdOf<T>;
+		fn ratio(token_a: Self::AssetId, token_b: Self::AssetId) -> Result<Perbill, DispatchError> {
+			let pool_key = AssetPair::new(token_a.clone(), token_b.clone());
+			Self::ensure_assets_exist(token_a, token_b)?;
+			ensure!(token_a != token_b, Error::<T>::IdenticalAssets);
+			let pool = <LiquidityPools<T>>::get(pool_key.clone())
+				.ok_or_else(|| DispatchError::from(Error::<T>::LiquidityPoolDoesNotExist))?;
+
+			let (token_a_reserve, token_b_reserve) = if token_a == pool_key.asset_a {
+				(pool.asset_a_balance, pool.asset_b_balance)
+			} else {
+				(pool.asset_b_balance, pool.asset_a_balance)
+			};
+			Ok(Perbill::from_rational(token_a_reserve, token_b_reserve))
+		}
+
+		fn ratio_fixed(
+			token_a: Self::AssetId,
+			token_b: Self::AssetId,
+		) -> Result<FixedU128, DispatchError> {
+			let (token_a_reserve, token_b_reserve) = Self::get_reserves(token_a, token_b)?;
+			let token_a_reserve: u128 = token_a_reserve.unique_saturated_into();
+			let token_b_reserve: u128 = token_b_reserve.unique_saturated_into();
+			let raw_ratio = FixedU128::checked_from_rational(token_a_reserve, token_b_reserve)
+				.ok_or(Error::<T>::InsufficientLiquidity)?;
+
+			// Scale the ratio of the raw reserves to whole units of both tokens
+			let decimals_a = Self::asset_decimals(token_a);
+			let decimals_b = Self::asset_decimals(token_b);
+			let scale = |decimals: u32| {
+				10u128
+					.checked_pow(decimals)
+					.and_then(FixedU128::checked_from_integer)
+					.ok_or(Error::<T>::Arithmetic)
+			};
+			let ratio = if decimals_b >= decimals_a {
+				raw_ratio.checked_mul(&scale(decimals_b - decimals_a)?)
+			} else {
+				raw_ratio.checked_div(&scale(decimals_a - decimals_b)?)
+			};
+
+			ratio.ok_or_else(|| Error::<T>::Arithmetic.into())
+		}
+	}
+
+	impl<T: Config> traits::TwapOracle for Pallet<T> {
+		type AssetId = NativeOrAssetIdOf<T>;
+
+		fn consult(
+			asset_in: Self::AssetId,
+			asset_out: Self::AssetId,
+			window: u64,
+		) -> Result<FixedU128, DispatchError> {
+			Self::ensure_assets_exist(asset_in, asset_out)?;
+			ensure!(asset_in != asset_out, Error::<T>::IdenticalAssets);
+
+			let (pool_key, pool) = Self::get_pool(asset_in, asset_out)
+				.ok_or_else(|| DispatchError::from(Error::<T>::LiquidityPoolDoesNotExist))?;
+
+			// Average from the latest observation that is at least `window` seconds old
+			let now = Self::current_timestamp();
+			let observations = <PriceObservations<T>>::get(&pool_key);
+			let start = observations
+				.iter()
+				.rev()
+				.find(|observation| observation.timestamp.saturating_add(window) <= now)
+				.ok_or(Error::<T>::NoPriceObservation)?;
+			let elapsed = now.saturating_sub(start.timestamp);
+			ensure!(elapsed > 0, Error::<T>::NoPriceObservation);
+
+			let (price_a_cumulative, price_b_cumulative) = pool.price_cumulatives_at(now);
+			let (cumulative, start_cumulative) = if asset_in == pool_key.asset_a {
+				(price_a_cumulative, start.price_a_cumulative)
+			} else {
+				(price_b_cumulative, start.price_b_cumulative)
+			};
+			// The accumulators wrap around, so only their difference is meaningful
+			let growth = cumulative.into_inner().wrapping_sub(start_cumulative.into_inner());
+			Ok(FixedU128::from_inner(growth / u128::from(elapsed)))
+		}
+	}
+
+	impl<T: Config> traits::OraclePrice for Pallet<T> {
+		type AssetId = NativeOrAssetIdOf<T>;
+		type Balance = AssetBalanceOf<T>;
+
+		fn get_price_for(
+			asset_in: Self::AssetId,
+			amount_in: Self::Balance,
+			asset_out: Self::AssetId,
+		) -> Result<Self::Balance, DispatchError> {
+			Self::ensure_assets_exist(asset_in, asset_out)?;
+			ensure!(asset_in != asset_out, Error::<T>::IdenticalAssets);
+
+			let pool_key = AssetPair::new(asset_in.clone(), asset_out.clone());
+			let pool = <LiquidityPools<T>>::get(pool_key.clone())
+				.ok_or_else(|| DispatchError::from(Error::<T>::LiquidityPoolDoesNotExist))?;
+
+			let (reserve_in, reserve_out) = if asset_in == pool_key.asset_a {
+				(pool.asset_a_balance, pool.asset_b_balance)
 			} else {
 				(pool.asset_b_balance, pool.asset_a_balance)
 			};
 
 			pool.calculate_output_for(amount_in, reserve_in, reserve_out)
 		}
+
+		fn get_input_for(
+			asset_in: Self::AssetId,
+			asset_out: Self::AssetId,
+			amount_out: Self::Balance,
+		) -> Result<Self::Balance, DispatchError> {
+			Self::ensure_assets_exist(asset_in, asset_out)?;
+			ensure!(asset_in != asset_out, Error::<T>::IdenticalAssets);
+
+			let (pool_key, pool) = Self::get_pool(asset_in, asset_out)
+				.ok_or_else(|| DispatchError::from(Error::<T>::LiquidityPoolDoesNotExist))?;
+
+			let (reserve_in, reserve_out) = if asset_in == pool_key.asset_a {
+				(pool.asset_a_balance, pool.asset_b_balance)
+			} else {
+				(pool.asset_b_balance, pool.asset_a_balance)
+			};
+
+			pool.calculate_input_for(amount_out, reserve_in, reserve_out)
+		}
+
+		fn find_best_route(
+			asset_in: Self::AssetId,
+			amount_in: Self::Balance,
+			asset_out: Self::AssetId,
+			max_hops: u32,
+		) -> Result<(Vec<Self::AssetId>, Self::Balance), DispatchError> {
+			let max_pools = T::MaxPoolsScanned::get() as usize;
+			Self::do_find_best_route(asset_in, amount_in, asset_out, max_hops, max_pools)
+		}
+
+		fn find_best_route_weight() -> Weight {
+			// Both assets and `TradingEnabled`, then each pool and whether it's paused
+			T::DbWeight::get().reads(3 + 2 * T::MaxPoolsScanned::get() as u64)
+		}
 	}
 }
@@ -0,0 +1,4 @@
+//! Storage migrations of pallet-dex, each one moving the storage to the version of its module.
+
+pub mod v1;
+pub mod v2;
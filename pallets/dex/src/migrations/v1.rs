@@ -0,0 +1,121 @@
+//! Migration from the storage of the first release, in which the pools only held assets of
+//! `Fungibles` and had no fee tier, creation deposit or price accumulators.
+
+use crate::types::AssetPair;
+use crate::*;
+#[cfg(feature = "try-runtime")]
+use codec::{Decode, Encode};
+#[cfg(feature = "try-runtime")]
+use frame_support::ensure;
+use frame_support::traits::{Get, GetStorageVersion, OnRuntimeUpgrade, StorageVersion};
+use frame_support::weights::Weight;
+use sp_runtime::traits::Zero;
+use sp_runtime::{FixedU128, Permill};
+use sp_std::marker::PhantomData;
+use sp_std::vec::Vec;
+
+/// The storage of the pools before version 1.
+pub(crate) mod v0 {
+	use super::*;
+	use codec::{Decode, Encode};
+	use frame_support::pallet_prelude::StorageMap;
+	use frame_support::{storage_alias, Blake2_128Concat};
+
+	#[derive(Encode, Decode)]
+	pub struct AssetPair<AssetId> {
+		pub asset_a: AssetId,
+		pub asset_b: AssetId,
+	}
+
+	#[derive(Encode, Decode)]
+	pub struct LiquidityPool<AccountId, AssetId, Balance> {
+		pub id: AssetId,
+		pub manager: AccountId,
+		pub asset_a_balance: Balance,
+		pub asset_b_balance: Balance,
+	}
+
+	#[storage_alias]
+	pub type LiquidityPools<T: Config> = StorageMap<
+		Pallet<T>,
+		Blake2_128Concat,
+		AssetPair<AssetIdOf<T>>,
+		LiquidityPool<AccountIdOf<T>, AssetIdOf<T>, AssetBalanceOf<T>>,
+	>;
+}
+
+/// The swap fee every pool charged before fee tiers.
+const V0_FEE: Permill = Permill::from_parts(3_000);
+
+/// Migrates the pools to storage version 1. Their assets become `NativeOrAssetId::Asset` and
+/// they get the fee they were charged as fee tier, fresh price accumulators and no creation
+/// deposit, as none was held when they were created.
+///
+/// It only runs on storage version 0.
+pub struct MigrateToV1<T>(PhantomData<T>);
+
+impl<T: Config> OnRuntimeUpgrade for MigrateToV1<T> {
+	fn on_runtime_upgrade() -> Weight {
+		let on_chain_version = Pallet::<T>::on_chain_storage_version();
+		if on_chain_version != 0 {
+			log::info!(
+				target: LOG_TARGET,
+				"Skipping the migration to v1, the storage is at {:?}",
+				on_chain_version
+			);
+			return T::DbWeight::get().reads(1);
+		}
+
+		let now = Pallet::<T>::current_timestamp();
+		// The old keys are removed before writing the new ones under the same prefix
+		let old_pools: Vec<_> = v0::LiquidityPools::<T>::drain().collect();
+		let migrated = old_pools.len() as u64;
+		for (old_key, old_pool) in old_pools {
+			let pool_key = AssetPair::<T>::new(old_key.asset_a.into(), old_key.asset_b.into());
+			let pool = LiquidityPool {
+				id: old_pool.id,
+				manager: old_pool.manager.clone(),
+				asset_a_balance: old_pool.asset_a_balance,
+				asset_b_balance: old_pool.asset_b_balance,
+				fee: V0_FEE,
+				k_last: Zero::zero(),
+				depositor: old_pool.manager,
+				deposit: Zero::zero(),
+				price_a_cumulative_last: FixedU128::zero(),
+				price_b_cumulative_last: FixedU128::zero(),
+				block_timestamp_last: now,
+			};
+			LiquidityPools::<T>::insert(&pool_key, pool);
+		}
+		StorageVersion::new(1).put::<Pallet<T>>();
+		log::info!(target: LOG_TARGET, "Migrated {} pools to v1", migrated);
+
+		// The version and the timestamp, then each old pool removed and the new one written
+		T::DbWeight::get().reads_writes(migrated + 2, migrated * 2 + 1)
+	}
+
+	#[cfg(feature = "try-runtime")]
+	fn pre_upgrade() -> Result<Vec<u8>, sp_runtime::TryRuntimeError> {
+		let pools = if Pallet::<T>::on_chain_storage_version() == 0 {
+			v0::LiquidityPools::<T>::iter().count()
+		} else {
+			LiquidityPools::<T>::iter().count()
+		};
+		Ok((pools as u32).encode())
+	}
+
+	#[cfg(feature = "try-runtime")]
+	fn post_upgrade(state: Vec<u8>) -> Result<(), sp_runtime::TryRuntimeError> {
+		let pools = u32::decode(&mut &state[..])
+			.map_err(|_| "Failed to decode the number of pools before the upgrade")?;
+		ensure!(
+			Pallet::<T>::on_chain_storage_version() >= 1,
+			"The storage version wasn't updated to v1"
+		);
+		ensure!(
+			LiquidityPools::<T>::iter().count() as u32 == pools,
+			"Pools were lost in the migration to v1"
+		);
+		Ok(())
+	}
+}
@@ -0,0 +1,216 @@
+//! Migration to per pool accounts, before which every pool kept its assets in the pallet account.
+
+use crate::types::AssetPair;
+use crate::*;
+#[cfg(feature = "try-runtime")]
+use frame_support::ensure;
+use frame_support::storage::with_storage_layer;
+use frame_support::traits::{
+	tokens::{Fortitude, Preservation},
+	Get, GetStorageVersion, OnRuntimeUpgrade, StorageVersion,
+};
+use frame_support::weights::Weight;
+use sp_runtime::helpers_128bit::multiply_by_rational_with_rounding;
+use sp_runtime::traits::{Saturating, UniqueSaturatedInto, Zero};
+use sp_runtime::{DispatchResult, Rounding};
+use sp_std::collections::btree_map::BTreeMap;
+use sp_std::marker::PhantomData;
+use sp_std::vec::Vec;
+
+/// Moves the reserves and the locked minimum liquidity of every pool created before each pool had
+/// its own account out of the pallet account they share, into the account of the pool.
+///
+/// The reserves recorded for an asset don't always add up to the balance of the shared account,
+/// for example when it was sent assets directly. So the balance of each asset that can be moved
+/// out of the shared account is split between the pools recorded in it in proportion to their
+/// reserves, and each pool is synced to the share it gets. The native currency is the exception,
+/// as the shared account also holds the endowment it got at genesis: the pools only get up to
+/// their reserves of it.
+///
+/// Nobody paid the existential deposit of the accounts of these pools when they were created. The
+/// shared account pays it out of that endowment, so that the pool accounts are kept alive like the
+/// ones `create_pool` funds without minting any native currency.
+///
+/// It only runs on storage version 1. A pool whose funds can't be moved is left as it was and
+/// logged.
+pub struct MigrateToV2<T>(PhantomData<T>);
+
+/// What is left of the recorded reserves of an asset in a shared account, and of its balance to
+/// split between them.
+struct Split<T: Config> {
+	reserves: AssetBalanceOf<T>,
+	balance: AssetBalanceOf<T>,
+}
+
+impl<T: Config> Split<T> {
+	/// Takes the share of the balance of a pool that recorded `reserve`. The last pool to take its
+	/// share gets all of the balance left, including what was rounded down for the others.
+	fn take(&mut self, reserve: AssetBalanceOf<T>) -> AssetBalanceOf<T> {
+		let share = if reserve.is_zero() {
+			Zero::zero()
+		} else if reserve >= self.reserves {
+			self.balance
+		} else {
+			multiply_by_rational_with_rounding(
+				self.balance.unique_saturated_into(),
+				reserve.unique_saturated_into(),
+				self.reserves.unique_saturated_into(),
+				Rounding::Down,
+			)
+			.map(|share| share.unique_saturated_into())
+			.unwrap_or_else(Zero::zero)
+		};
+		self.reserves = self.reserves.saturating_sub(reserve);
+		self.balance = self.balance.saturating_sub(share);
+		share
+	}
+}
+
+impl<T: Config> MigrateToV2<T> {
+	fn move_pool_funds(
+		pool_key: &AssetPair<T>,
+		pool: &mut LiquidityPool<T>,
+		pool_account: &AccountIdOf<T>,
+		shares: [AssetBalanceOf<T>; 2],
+	) -> DispatchResult {
+		let existential_deposit = <T::NativeBalance as fungible::Inspect<_>>::minimum_balance();
+		<T::NativeBalance as fungible::Mutate<_>>::transfer(
+			&pool.manager,
+			pool_account,
+			existential_deposit,
+			Preservation::Preserve,
+		)?;
+
+		for (asset, share) in [pool_key.asset_a, pool_key.asset_b].into_iter().zip(shares) {
+			if !share.is_zero() {
+				NativeOrAssetAdapter::<T>::transfer(
+					asset,
+					&pool.manager,
+					pool_account,
+					share,
+					Preservation::Expendable,
+				)?;
+			}
+		}
+
+		// Only the pallet account held LP tokens of the pool, the locked minimum liquidity
+		let locked_liquidity = T::Fungibles::balance(pool.id, &pool.manager);
+		if !locked_liquidity.is_zero() {
+			T::Fungibles::transfer(
+				pool.id,
+				&pool.manager,
+				pool_account,
+				locked_liquidity,
+				Preservation::Expendable,
+			)?;
+		}
+
+		pool.manager = pool_account.clone();
+		// An empty pool has nothing to sync, its reserves stay zero
+		if !pool.asset_a_balance.is_zero() && !pool.asset_b_balance.is_zero() {
+			pool.sync(pool_key)?;
+		}
+		Ok(())
+	}
+}
+
+impl<T: Config> OnRuntimeUpgrade for MigrateToV2<T> {
+	fn on_runtime_upgrade() -> Weight {
+		let on_chain_version = Pallet::<T>::on_chain_storage_version();
+		if on_chain_version != 1 {
+			log::info!(
+				target: LOG_TARGET,
+				"Skipping the migration to v2, the storage is at {:?}",
+				on_chain_version
+			);
+			return T::DbWeight::get().reads(1);
+		}
+
+		let mut reads = 1u64;
+		let mut writes = 1u64;
+
+		let pools: Vec<_> = LiquidityPools::<T>::iter().collect();
+		reads += pools.len() as u64;
+		let pools: Vec<_> = pools
+			.into_iter()
+			.filter(|(_, pool)| pool.manager != Pallet::<T>::pool_account(pool.id))
+			.collect();
+
+		let mut splits = BTreeMap::<_, Split<T>>::new();
+		for (pool_key, pool) in &pools {
+			for (asset, reserve) in
+				[(pool_key.asset_a, pool.asset_a_balance), (pool_key.asset_b, pool.asset_b_balance)]
+			{
+				let split = splits.entry((pool.manager.clone(), asset)).or_insert_with(|| {
+					reads += 1;
+					Split {
+						reserves: Zero::zero(),
+						balance: NativeOrAssetAdapter::<T>::reducible_balance(
+							asset,
+							&pool.manager,
+							Preservation::Expendable,
+							Fortitude::Polite,
+						),
+					}
+				});
+				split.reserves = split.reserves.saturating_add(reserve);
+			}
+		}
+		for ((_, asset), split) in splits.iter_mut() {
+			if *asset == NativeOrAssetId::Native {
+				split.balance = split.balance.min(split.reserves);
+			}
+		}
+
+		for (pool_key, mut pool) in pools {
+			let shares = [
+				(pool_key.asset_a, pool.asset_a_balance),
+				(pool_key.asset_b, pool.asset_b_balance),
+			]
+			.map(|(asset, reserve)| {
+				splits
+					.get_mut(&(pool.manager.clone(), asset))
+					.map_or_else(Zero::zero, |split| split.take(reserve))
+			});
+			let pool_account = Pallet::<T>::pool_account(pool.id);
+
+			// The existential deposit, the LP token and both assets with their source and
+			// destination, and the sync
+			reads += 10;
+			writes += 10;
+			let moved = with_storage_layer(|| {
+				Self::move_pool_funds(&pool_key, &mut pool, &pool_account, shares)
+			});
+			match moved {
+				Ok(()) => {
+					LiquidityPools::<T>::insert(&pool_key, pool);
+					writes += 1;
+				},
+				Err(error) => log::error!(
+					target: LOG_TARGET,
+					"Failed to move the funds of pool {:?} to its own account: {:?}",
+					pool.id,
+					error
+				),
+			}
+		}
+		StorageVersion::new(2).put::<Pallet<T>>();
+
+		T::DbWeight::get().reads_writes(reads, writes)
+	}
+
+	#[cfg(feature = "try-runtime")]
+	fn post_upgrade(_state: Vec<u8>) -> Result<(), sp_runtime::TryRuntimeError> {
+		ensure!(
+			Pallet::<T>::on_chain_storage_version() >= 2,
+			"The storage version wasn't updated to v2"
+		);
+		for (_, pool) in LiquidityPools::<T>::iter() {
+			ensure!(
+				pool.manager == Pallet::<T>::pool_account(pool.id),
+				"A pool still uses the shared pallet account"
+			);
+		}
+		Pallet::<T>::do_try_state()
+	}
+}
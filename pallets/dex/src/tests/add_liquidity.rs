@@ -13,19 +13,26 @@ fn mint_works() {
 
 	ExtBuilder::default()
 		.with_endowed_balances(vec![(asset_a, ALICE, amount_a), (asset_b, ALICE, amount_b)])
-		.build()
-		.execute_with(|| {
+		.build_and_execute(|| {
 			let expected_liquidity = expand_to_decimals(2u128);
+			assert_ok!(Dex::create_pool(
+				RuntimeOrigin::signed(ALICE.into()),
+				asset_a.into(),
+				asset_b.into(),
+				DEFAULT_FEE_TIER
+			));
 			assert_ok!(Dex::mint(
 				RuntimeOrigin::signed(ALICE.into()),
-				pool_id,
-				asset_a,
-				asset_b,
+				asset_a.into(),
+				asset_b.into(),
 				amount_a,
-				amount_b
+				amount_b,
+				0,
+				0,
+				None
 			));
 
-			let pool_key = AssetPair::new(asset_a, asset_b);
+			let pool_key = AssetPair::new(asset_a.into(), asset_b.into());
 			let pool = LiquidityPools::<Test>::get(pool_key).unwrap();
 
 			// Internal balances should be updated
@@ -46,10 +53,26 @@ fn mint_works() {
 
 			// Ensure correct events are triggered
 			frame_system::Pallet::<Test>::assert_has_event(RuntimeEvent::Dex(
-				Event::LiquidityPoolCreated(pool_id, asset_a, asset_b),
+				Event::LiquidityPoolCreated {
+					creator: ALICE,
+					pool_id,
+					asset_a: asset_a.into(),
+					asset_b: asset_b.into(),
+					fee: DEFAULT_FEE_TIER,
+				},
 			));
 			frame_system::Pallet::<Test>::assert_has_event(RuntimeEvent::Dex(
-				Event::LiquidityAdded(asset_a, asset_b, amount_a, amount_b),
+				Event::LiquidityAdded {
+					who: ALICE,
+					pool_id,
+					asset_a: asset_a.into(),
+					asset_b: asset_b.into(),
+					amount_a,
+					amount_b,
+					lp_minted: expected_liquidity - MIN_LIQUIDITY,
+					reserve_a: amount_a,
+					reserve_b: amount_b,
+				},
 			));
 		});
 }
@@ -59,8 +82,6 @@ fn mint_works_with_same_asset_in_multiple_pools() {
 	let asset_a: AssetId = 1001;
 	let asset_b: AssetId = 1002;
 	let asset_c: AssetId = 1003;
-	let pool_id: AssetId = 10000;
-	let pool_id_2: AssetId = 10001;
 	let total: u128 = expand_to_decimals(100u128);
 	let amount_a: u128 = expand_to_decimals(1u128);
 	let amount_b: u128 = expand_to_decimals(4u128);
@@ -73,40 +94,58 @@ fn mint_works_with_same_asset_in_multiple_pools() {
 			(asset_b, ALICE, total),
 			(asset_c, ALICE, total),
 		])
-		.build()
-		.execute_with(|| {
+		.build_and_execute(|| {
 			// Create pool for A - B
+			assert_ok!(Dex::create_pool(
+				RuntimeOrigin::signed(ALICE.into()),
+				asset_a.into(),
+				asset_b.into(),
+				DEFAULT_FEE_TIER
+			));
 			assert_ok!(Dex::mint(
 				RuntimeOrigin::signed(ALICE.into()),
-				pool_id,
-				asset_a,
-				asset_b,
+				asset_a.into(),
+				asset_b.into(),
 				amount_a,
-				amount_b
+				amount_b,
+				0,
+				0,
+				None
 			));
 
 			// Create pool for A - C
+			assert_ok!(Dex::create_pool(
+				RuntimeOrigin::signed(ALICE.into()),
+				asset_a.into(),
+				asset_c.into(),
+				DEFAULT_FEE_TIER
+			));
 			assert_ok!(Dex::mint(
 				RuntimeOrigin::signed(ALICE.into()),
-				pool_id_2,
-				asset_a,
-				asset_c,
+				asset_a.into(),
+				asset_c.into(),
 				amount_a,
-				amount_c
+				amount_c,
+				0,
+				0,
+				None
 			));
 
 			assert_ok!(Dex::burn(
 				RuntimeOrigin::signed(ALICE.into()),
-				asset_a,
-				asset_c,
-				burn_amount
+				asset_a.into(),
+				asset_c.into(),
+				burn_amount,
+				0,
+				0,
+				None
 			));
 
 			// Removing from one pool shouldn't affect the reserves in another
-			let first_pool_key = AssetPair::new(asset_a, asset_b);
+			let first_pool_key = AssetPair::new(asset_a.into(), asset_b.into());
 			let first_pool = LiquidityPools::<Test>::get(first_pool_key).unwrap();
 
-			let second_pool_key = AssetPair::new(asset_a, asset_c);
+			let second_pool_key = AssetPair::new(asset_a.into(), asset_c.into());
 			let second_pool = LiquidityPools::<Test>::get(second_pool_key).unwrap();
 
 			// Balances in the first pool should not be affected
@@ -128,7 +167,6 @@ fn mint_works_increments_counter_on_multiple_pools() {
 	let asset_a: AssetId = 1001;
 	let asset_b: AssetId = 1002;
 	let asset_c: AssetId = 1003;
-	let pool_id: AssetId = 10000;
 	let pool_id_2: AssetId = 10001;
 	let total: u128 = expand_to_decimals(10u128);
 	let amount_a: u128 = expand_to_decimals(1u128);
@@ -140,29 +178,44 @@ fn mint_works_increments_counter_on_multiple_pools() {
 			(asset_b, ALICE, total),
 			(asset_c, ALICE, total),
 		])
-		.build()
-		.execute_with(|| {
+		.build_and_execute(|| {
 			let expected_liquidity = expand_to_decimals(2u128);
 			// Create the first pool
+			assert_ok!(Dex::create_pool(
+				RuntimeOrigin::signed(ALICE.into()),
+				asset_a.into(),
+				asset_b.into(),
+				DEFAULT_FEE_TIER
+			));
 			assert_ok!(Dex::mint(
 				RuntimeOrigin::signed(ALICE.into()),
-				pool_id,
-				asset_a,
-				asset_b,
+				asset_a.into(),
+				asset_b.into(),
 				amount_a,
-				amount_b
+				amount_b,
+				0,
+				0,
+				None
 			));
 
+			assert_ok!(Dex::create_pool(
+				RuntimeOrigin::signed(ALICE.into()),
+				asset_a.into(),
+				asset_c.into(),
+				DEFAULT_FEE_TIER
+			));
 			assert_ok!(Dex::mint(
 				RuntimeOrigin::signed(ALICE.into()),
-				pool_id_2,
-				asset_a,
-				asset_c,
+				asset_a.into(),
+				asset_c.into(),
 				amount_a,
-				amount_b
+				amount_b,
+				0,
+				0,
+				None
 			));
 
-			let pool_key = AssetPair::new(asset_a, asset_c);
+			let pool_key = AssetPair::new(asset_a.into(), asset_c.into());
 			let pool = LiquidityPools::<Test>::get(pool_key).unwrap();
 
 			// Minting of LP Tokens occurred
@@ -177,38 +230,48 @@ fn mint_works_with_existing_pool() {
 	let asset_a: AssetId = 1001;
 	let asset_b: AssetId = 1002;
 	let pool_id: AssetId = 10000;
-	let pool_id_2: AssetId = 10001;
 	let total_a: u128 = expand_to_decimals(100u128);
 	let total_b: u128 = expand_to_decimals(100u128);
 	let amount_a: u128 = expand_to_decimals(10u128);
 	let amount_b: u128 = expand_to_decimals(40u128);
 	let second_amount_a: u128 = expand_to_decimals(50u128);
 	let second_amount_b: u128 = expand_to_decimals(10u128);
+	// Only the amount of asset_a matching the 10:40 pool ratio is taken
+	let used_amount_a: u128 = expand_to_decimals(25u128) / 10;
 
 	ExtBuilder::default()
 		.with_endowed_balances(vec![(asset_a, ALICE, total_a), (asset_b, ALICE, total_b)])
-		.build()
-		.execute_with(|| {
+		.build_and_execute(|| {
 			let expected_liquidity = expand_to_decimals(25u128);
+			assert_ok!(Dex::create_pool(
+				RuntimeOrigin::signed(ALICE.into()),
+				asset_a.into(),
+				asset_b.into(),
+				DEFAULT_FEE_TIER
+			));
 			assert_ok!(Dex::mint(
 				RuntimeOrigin::signed(ALICE.into()),
-				pool_id,
-				asset_a,
-				asset_b,
+				asset_a.into(),
+				asset_b.into(),
 				amount_a,
-				amount_b
+				amount_b,
+				0,
+				0,
+				None
 			));
-			let pool_key = AssetPair { asset_a, asset_b };
+			let pool_key = AssetPair { asset_a: asset_a.into(), asset_b: asset_b.into() };
 			let pool = LiquidityPools::<Test>::get(pool_key).unwrap();
 			assert_eq!(Fungibles::total_supply(pool.id), expand_to_decimals(20u128));
 
 			assert_ok!(Dex::mint(
 				RuntimeOrigin::signed(ALICE.into()),
-				pool_id_2,
-				asset_a,
-				asset_b,
+				asset_a.into(),
+				asset_b.into(),
 				second_amount_a,
-				second_amount_b
+				second_amount_b,
+				0,
+				0,
+				None
 			));
 
 			// Minting of LP Tokens occurred
@@ -216,19 +279,35 @@ fn mint_works_with_existing_pool() {
 			assert_eq!(Fungibles::balance(pool.id, ALICE), expected_liquidity - MIN_LIQUIDITY);
 
 			// User balances have been updated
-			assert_eq!(Fungibles::balance(asset_a, ALICE), total_a - amount_a - second_amount_a);
+			assert_eq!(Fungibles::balance(asset_a, ALICE), total_a - amount_a - used_amount_a);
 			assert_eq!(Fungibles::balance(asset_b, ALICE), total_b - amount_b - second_amount_b);
 
 			// Pallet manager balances have been updated
-			assert_eq!(Fungibles::balance(asset_a, pool.manager), amount_a + second_amount_a);
+			assert_eq!(Fungibles::balance(asset_a, pool.manager), amount_a + used_amount_a);
 			assert_eq!(Fungibles::balance(asset_b, pool.manager), amount_b + second_amount_b);
 
 			// Ensure correct events are triggered
 			frame_system::Pallet::<Test>::assert_has_event(RuntimeEvent::Dex(
-				Event::LiquidityPoolCreated(pool_id, asset_a, asset_b),
+				Event::LiquidityPoolCreated {
+					creator: ALICE,
+					pool_id,
+					asset_a: asset_a.into(),
+					asset_b: asset_b.into(),
+					fee: DEFAULT_FEE_TIER,
+				},
 			));
 			frame_system::Pallet::<Test>::assert_has_event(RuntimeEvent::Dex(
-				Event::LiquidityAdded(asset_a, asset_b, second_amount_a, second_amount_b),
+				Event::LiquidityAdded {
+					who: ALICE,
+					pool_id,
+					asset_a: asset_a.into(),
+					asset_b: asset_b.into(),
+					amount_a: used_amount_a,
+					amount_b: second_amount_b,
+					lp_minted: expand_to_decimals(5u128),
+					reserve_a: amount_a + used_amount_a,
+					reserve_b: amount_b + second_amount_b,
+				},
 			));
 		});
 }
@@ -243,19 +322,26 @@ fn mint_sorting_works() {
 
 	ExtBuilder::default()
 		.with_endowed_balances(vec![(asset_a, ALICE, amount_a), (asset_b, ALICE, amount_b)])
-		.build()
-		.execute_with(|| {
+		.build_and_execute(|| {
 			let expected_liquidity = expand_to_decimals(2u128);
+			assert_ok!(Dex::create_pool(
+				RuntimeOrigin::signed(ALICE.into()),
+				asset_b.into(),
+				asset_a.into(),
+				DEFAULT_FEE_TIER
+			));
 			assert_ok!(Dex::mint(
 				RuntimeOrigin::signed(ALICE.into()),
-				pool_id,
-				asset_b,
-				asset_a,
+				asset_b.into(),
+				asset_a.into(),
 				amount_a,
-				amount_b
+				amount_b,
+				0,
+				0,
+				None
 			));
 
-			let pool_key = AssetPair { asset_a, asset_b };
+			let pool_key = AssetPair { asset_a: asset_a.into(), asset_b: asset_b.into() };
 			let pool = LiquidityPools::<Test>::get(pool_key).unwrap();
 
 			// Minting of LP Tokens occurred
@@ -272,10 +358,26 @@ fn mint_sorting_works() {
 
 			// Ensure correct events are triggered
 			frame_system::Pallet::<Test>::assert_has_event(RuntimeEvent::Dex(
-				Event::LiquidityPoolCreated(pool_id, asset_a, asset_b),
+				Event::LiquidityPoolCreated {
+					creator: ALICE,
+					pool_id,
+					asset_a: asset_a.into(),
+					asset_b: asset_b.into(),
+					fee: DEFAULT_FEE_TIER,
+				},
 			));
 			frame_system::Pallet::<Test>::assert_has_event(RuntimeEvent::Dex(
-				Event::LiquidityAdded(asset_a, asset_b, amount_a, amount_b),
+				Event::LiquidityAdded {
+					who: ALICE,
+					pool_id,
+					asset_a: asset_a.into(),
+					asset_b: asset_b.into(),
+					amount_a,
+					amount_b,
+					lp_minted: expected_liquidity - MIN_LIQUIDITY,
+					reserve_a: amount_a,
+					reserve_b: amount_b,
+				},
 			));
 		});
 }
@@ -284,22 +386,22 @@ fn mint_sorting_works() {
 fn mint_fails_with_invalid_assets() {
 	let asset_a: AssetId = 1001;
 	let asset_b: AssetId = 1002;
-	let pool_id: AssetId = 10000;
 	let amount_a: u128 = expand_to_decimals(1u128);
 	let amount_b: u128 = expand_to_decimals(4u128);
 
 	ExtBuilder::default()
 		.with_endowed_balances(vec![(asset_a, ALICE, amount_a), (asset_b, ALICE, amount_b)])
-		.build()
-		.execute_with(|| {
+		.build_and_execute(|| {
 			assert_noop!(
 				Dex::mint(
 					RuntimeOrigin::signed(ALICE.into()),
-					pool_id,
-					asset_a,
-					asset_a,
+					asset_a.into(),
+					asset_a.into(),
 					amount_a,
-					amount_b
+					amount_b,
+					0,
+					0,
+					None
 				),
 				Error::<Test>::IdenticalAssets
 			);
@@ -310,22 +412,22 @@ fn mint_fails_with_invalid_assets() {
 fn mint_fails_with_token_a_0_amount() {
 	let asset_a: AssetId = 1001;
 	let asset_b: AssetId = 1002;
-	let pool_id: AssetId = 10000;
 	let amount_a: u128 = expand_to_decimals(1u128);
 	let amount_b: u128 = expand_to_decimals(4u128);
 
 	ExtBuilder::default()
 		.with_endowed_balances(vec![(asset_a, ALICE, amount_a), (asset_b, ALICE, amount_b)])
-		.build()
-		.execute_with(|| {
+		.build_and_execute(|| {
 			assert_noop!(
 				Dex::mint(
 					RuntimeOrigin::signed(ALICE.into()),
-					pool_id,
-					asset_a,
-					asset_b,
+					asset_a.into(),
+					asset_b.into(),
+					0,
+					amount_b,
+					0,
 					0,
-					amount_b
+					None
 				),
 				Error::<Test>::InsufficientInputAmount
 			);
@@ -336,22 +438,22 @@ fn mint_fails_with_token_a_0_amount() {
 fn mint_fails_with_token_b_0_amount() {
 	let asset_a: AssetId = 1001;
 	let asset_b: AssetId = 1002;
-	let pool_id: AssetId = 10000;
 	let amount_a: u128 = expand_to_decimals(1u128);
 	let amount_b: u128 = expand_to_decimals(4u128);
 
 	ExtBuilder::default()
 		.with_endowed_balances(vec![(asset_a, ALICE, amount_a), (asset_b, ALICE, amount_b)])
-		.build()
-		.execute_with(|| {
+		.build_and_execute(|| {
 			assert_noop!(
 				Dex::mint(
 					RuntimeOrigin::signed(ALICE.into()),
-					pool_id,
-					asset_a,
-					asset_b,
+					asset_a.into(),
+					asset_b.into(),
 					amount_a,
-					0
+					0,
+					0,
+					0,
+					None
 				),
 				Error::<Test>::InsufficientInputAmount
 			);
@@ -362,22 +464,28 @@ fn mint_fails_with_token_b_0_amount() {
 fn mint_fails_with_insufficient_liquidity() {
 	let asset_a: AssetId = 1001;
 	let asset_b: AssetId = 1002;
-	let pool_id: AssetId = 10000;
 	let amount_a: u128 = 1;
 	let amount_b: u128 = 4;
 
 	ExtBuilder::default()
 		.with_endowed_balances(vec![(asset_a, ALICE, amount_a), (asset_b, ALICE, amount_b)])
-		.build()
-		.execute_with(|| {
+		.build_and_execute(|| {
+			assert_ok!(Dex::create_pool(
+				RuntimeOrigin::signed(ALICE.into()),
+				asset_a.into(),
+				asset_b.into(),
+				DEFAULT_FEE_TIER
+			));
 			assert_noop!(
 				Dex::mint(
 					RuntimeOrigin::signed(ALICE.into()),
-					pool_id,
-					asset_a,
-					asset_b,
+					asset_a.into(),
+					asset_b.into(),
 					amount_a,
-					amount_b
+					amount_b,
+					0,
+					0,
+					None
 				),
 				Error::<Test>::InsufficientLiquidity
 			);
@@ -388,22 +496,22 @@ fn mint_fails_with_insufficient_liquidity() {
 fn mint_fails_with_unknown_asset_id_a() {
 	let asset_a: AssetId = 1001;
 	let asset_b: AssetId = 1002;
-	let pool_id: AssetId = 10000;
 	let amount_a: u128 = 1;
 	let amount_b: u128 = 4;
 
 	ExtBuilder::default()
 		.with_endowed_balances(vec![(asset_a, ALICE, amount_a)])
-		.build()
-		.execute_with(|| {
+		.build_and_execute(|| {
 			assert_noop!(
 				Dex::mint(
 					RuntimeOrigin::signed(ALICE.into()),
-					pool_id,
-					asset_a,
-					asset_b,
+					asset_a.into(),
+					asset_b.into(),
 					amount_a,
-					amount_b
+					amount_b,
+					0,
+					0,
+					None
 				),
 				Error::<Test>::UnknownAssetId
 			);
@@ -414,24 +522,167 @@ fn mint_fails_with_unknown_asset_id_a() {
 fn mint_fails_with_unknown_asset_id_b() {
 	let asset_a: AssetId = 1001;
 	let asset_b: AssetId = 1002;
-	let pool_id: AssetId = 10000;
 	let amount_a: u128 = 1;
 	let amount_b: u128 = 4;
 
 	ExtBuilder::default()
 		.with_endowed_balances(vec![(asset_b, ALICE, amount_b)])
-		.build()
-		.execute_with(|| {
+		.build_and_execute(|| {
 			assert_noop!(
 				Dex::mint(
 					RuntimeOrigin::signed(ALICE.into()),
-					pool_id,
-					asset_a,
-					asset_b,
+					asset_a.into(),
+					asset_b.into(),
 					amount_a,
-					amount_b
+					amount_b,
+					0,
+					0,
+					None
 				),
 				Error::<Test>::UnknownAssetId
 			);
 		});
 }
+
+#[test]
+fn mint_fails_after_deadline() {
+	let asset_a: AssetId = 1001;
+	let asset_b: AssetId = 1002;
+	let amount_a: u128 = expand_to_decimals(1u128);
+	let amount_b: u128 = expand_to_decimals(4u128);
+
+	ExtBuilder::default()
+		.with_endowed_balances(vec![(asset_a, ALICE, amount_a), (asset_b, ALICE, amount_b)])
+		.build_and_execute(|| {
+			System::set_block_number(5);
+			assert_noop!(
+				Dex::mint(
+					RuntimeOrigin::signed(ALICE.into()),
+					asset_a.into(),
+					asset_b.into(),
+					amount_a,
+					amount_b,
+					0,
+					0,
+					Some(4)
+				),
+				Error::<Test>::Expired
+			);
+		});
+}
+
+#[test]
+fn mint_fails_without_pool() {
+	let asset_a: AssetId = 1001;
+	let asset_b: AssetId = 1002;
+	let amount_a: u128 = expand_to_decimals(1u128);
+	let amount_b: u128 = expand_to_decimals(4u128);
+
+	ExtBuilder::default()
+		.with_endowed_balances(vec![(asset_a, ALICE, amount_a), (asset_b, ALICE, amount_b)])
+		.build_and_execute(|| {
+			assert_noop!(
+				Dex::mint(
+					RuntimeOrigin::signed(ALICE.into()),
+					asset_a.into(),
+					asset_b.into(),
+					amount_a,
+					amount_b,
+					0,
+					0,
+					None
+				),
+				Error::<Test>::LiquidityPoolDoesNotExist
+			);
+		});
+}
+
+#[test]
+fn mint_fails_below_amount_a_min() {
+	let asset_a: AssetId = 1001;
+	let asset_b: AssetId = 1002;
+	let total: u128 = expand_to_decimals(100u128);
+	let amount_a: u128 = expand_to_decimals(10u128);
+	let amount_b: u128 = expand_to_decimals(40u128);
+
+	ExtBuilder::default()
+		.with_endowed_balances(vec![(asset_a, ALICE, total), (asset_b, ALICE, total)])
+		.build_and_execute(|| {
+			assert_ok!(Dex::create_pool(
+				RuntimeOrigin::signed(ALICE),
+				asset_a.into(),
+				asset_b.into(),
+				DEFAULT_FEE_TIER
+			));
+			assert_ok!(Dex::mint(
+				RuntimeOrigin::signed(ALICE),
+				asset_a.into(),
+				asset_b.into(),
+				amount_a,
+				amount_b,
+				0,
+				0,
+				None
+			));
+
+			// 10 of asset_b only match 2.5 of asset_a at the 10:40 pool ratio
+			assert_noop!(
+				Dex::mint(
+					RuntimeOrigin::signed(ALICE),
+					asset_a.into(),
+					asset_b.into(),
+					expand_to_decimals(5u128),
+					expand_to_decimals(10u128),
+					expand_to_decimals(3u128),
+					0,
+					None
+				),
+				Error::<Test>::InsufficientAmountA
+			);
+		});
+}
+
+#[test]
+fn mint_fails_below_amount_b_min() {
+	let asset_a: AssetId = 1001;
+	let asset_b: AssetId = 1002;
+	let total: u128 = expand_to_decimals(100u128);
+	let amount_a: u128 = expand_to_decimals(10u128);
+	let amount_b: u128 = expand_to_decimals(40u128);
+
+	ExtBuilder::default()
+		.with_endowed_balances(vec![(asset_a, ALICE, total), (asset_b, ALICE, total)])
+		.build_and_execute(|| {
+			assert_ok!(Dex::create_pool(
+				RuntimeOrigin::signed(ALICE),
+				asset_a.into(),
+				asset_b.into(),
+				DEFAULT_FEE_TIER
+			));
+			assert_ok!(Dex::mint(
+				RuntimeOrigin::signed(ALICE),
+				asset_a.into(),
+				asset_b.into(),
+				amount_a,
+				amount_b,
+				0,
+				0,
+				None
+			));
+
+			// 1 of asset_a only matches 4 of asset_b at the 10:40 pool ratio
+			assert_noop!(
+				Dex::mint(
+					RuntimeOrigin::signed(ALICE),
+					asset_a.into(),
+					asset_b.into(),
+					expand_to_decimals(1u128),
+					expand_to_decimals(10u128),
+					0,
+					expand_to_decimals(5u128),
+					None
+				),
+				Error::<Test>::InsufficientAmountB
+			);
+		});
+}
@@ -0,0 +1,343 @@
+use crate::tests::mock::*;
+use crate::types::AssetPair;
+use crate::{Error, Event, HoldReason, LiquidityPools, NextLpAssetId};
+use frame_support::traits::fungible::InspectHold;
+use frame_support::traits::Hooks;
+use frame_support::weights::Weight;
+use frame_support::{assert_noop, assert_ok};
+use sp_runtime::{DispatchError, Permill, TokenError};
+
+#[test]
+fn create_pool_works() {
+	let asset_a: AssetId = 1001;
+	let asset_b: AssetId = 1002;
+	let pool_id: AssetId = 10000;
+
+	ExtBuilder::default()
+		.with_endowed_balances(vec![(asset_a, ALICE, 1), (asset_b, ALICE, 1)])
+		.build_and_execute(|| {
+			assert_ok!(Dex::create_pool(
+				RuntimeOrigin::signed(ALICE),
+				asset_b.into(),
+				asset_a.into(),
+				DEFAULT_FEE_TIER
+			));
+
+			let pool_key = AssetPair::new(asset_a.into(), asset_b.into());
+			let pool = LiquidityPools::<Test>::get(pool_key).unwrap();
+
+			// The pool is empty and has its own LP token
+			assert_eq!(pool.id, pool_id);
+			assert_eq!(pool.asset_a_balance, 0);
+			assert_eq!(pool.asset_b_balance, 0);
+			assert_eq!(pool.fee, DEFAULT_FEE_TIER);
+			assert_eq!(Fungibles::total_supply(pool_id), 0);
+
+			// The creation deposit is held from the creator
+			assert_eq!(pool.depositor, ALICE);
+			assert_eq!(pool.deposit, POOL_DEPOSIT);
+			assert_eq!(
+				Balances::balance_on_hold(&HoldReason::PoolDeposit.into(), &ALICE),
+				POOL_DEPOSIT
+			);
+			assert_eq!(Balances::free_balance(ALICE), POOL_DEPOSIT * 9 - EXISTENTIAL_DEPOSIT);
+			// as well as the existential deposit of the pool account
+			assert_eq!(Balances::free_balance(pool.manager), EXISTENTIAL_DEPOSIT);
+
+			frame_system::Pallet::<Test>::assert_has_event(RuntimeEvent::Dex(
+				Event::LiquidityPoolCreated {
+					creator: ALICE,
+					pool_id,
+					asset_a: asset_a.into(),
+					asset_b: asset_b.into(),
+					fee: DEFAULT_FEE_TIER,
+				},
+			));
+		});
+}
+
+#[test]
+fn create_pool_fails_for_existing_pool() {
+	let asset_a: AssetId = 1001;
+	let asset_b: AssetId = 1002;
+
+	ExtBuilder::default()
+		.with_endowed_balances(vec![(asset_a, ALICE, 1), (asset_b, ALICE, 1)])
+		.build_and_execute(|| {
+			assert_ok!(Dex::create_pool(
+				RuntimeOrigin::signed(ALICE),
+				asset_a.into(),
+				asset_b.into(),
+				DEFAULT_FEE_TIER
+			));
+			assert_noop!(
+				Dex::create_pool(
+					RuntimeOrigin::signed(BOB),
+					asset_b.into(),
+					asset_a.into(),
+					Permill::from_parts(500)
+				),
+				Error::<Test>::LiquidityPoolExists
+			);
+		});
+}
+
+#[test]
+fn create_pool_fails_with_identical_assets() {
+	let asset_a: AssetId = 1001;
+
+	ExtBuilder::default()
+		.with_endowed_balances(vec![(asset_a, ALICE, 1)])
+		.build_and_execute(|| {
+			assert_noop!(
+				Dex::create_pool(
+					RuntimeOrigin::signed(ALICE),
+					asset_a.into(),
+					asset_a.into(),
+					DEFAULT_FEE_TIER
+				),
+				Error::<Test>::IdenticalAssets
+			);
+		});
+}
+
+#[test]
+fn create_pool_fails_with_unsupported_fee_tier() {
+	let asset_a: AssetId = 1001;
+	let asset_b: AssetId = 1002;
+
+	ExtBuilder::default()
+		.with_endowed_balances(vec![(asset_a, ALICE, 1), (asset_b, ALICE, 1)])
+		.build_and_execute(|| {
+			assert_noop!(
+				Dex::create_pool(
+					RuntimeOrigin::signed(ALICE),
+					asset_a.into(),
+					asset_b.into(),
+					Permill::from_percent(2)
+				),
+				Error::<Test>::UnsupportedFeeTier
+			);
+		});
+}
+
+#[test]
+fn create_pool_fails_without_deposit() {
+	let asset_a: AssetId = 1001;
+	let asset_b: AssetId = 1002;
+
+	ExtBuilder::default()
+		.with_endowed_balances(vec![(asset_a, ALICE, 1), (asset_b, ALICE, 1)])
+		.with_native_balances(vec![(ALICE, POOL_DEPOSIT - 1)])
+		.build_and_execute(|| {
+			assert_noop!(
+				Dex::create_pool(
+					RuntimeOrigin::signed(ALICE),
+					asset_a.into(),
+					asset_b.into(),
+					DEFAULT_FEE_TIER
+				),
+				TokenError::FundsUnavailable
+			);
+		});
+}
+
+#[test]
+fn create_pool_skips_lp_asset_ids_already_in_use() {
+	let asset_a: AssetId = 1001;
+	let asset_b: AssetId = 1002;
+	let taken_id: AssetId = 10000;
+
+	ExtBuilder::default()
+		.with_endowed_balances(vec![(asset_a, ALICE, 1), (asset_b, ALICE, 1), (taken_id, BOB, 1)])
+		.build_and_execute(|| {
+			assert_ok!(Dex::create_pool(
+				RuntimeOrigin::signed(ALICE),
+				asset_a.into(),
+				asset_b.into(),
+				DEFAULT_FEE_TIER
+			));
+
+			let pool_key = AssetPair::new(asset_a.into(), asset_b.into());
+			let pool = LiquidityPools::<Test>::get(pool_key).unwrap();
+			assert_eq!(pool.id, taken_id + 1);
+			assert_eq!(NextLpAssetId::<Test>::get(), Some(taken_id + 2));
+		});
+}
+
+#[test]
+fn create_pool_fails_when_lp_asset_ids_are_exhausted() {
+	let asset_a: AssetId = 1001;
+	let asset_b: AssetId = 1002;
+
+	ExtBuilder::default()
+		.with_endowed_balances(vec![(asset_a, ALICE, 1), (asset_b, ALICE, 1)])
+		.build_and_execute(|| {
+			NextLpAssetId::<Test>::put(MaxLpAssetId::get() + 1);
+
+			assert_noop!(
+				Dex::create_pool(
+					RuntimeOrigin::signed(ALICE),
+					asset_a.into(),
+					asset_b.into(),
+					DEFAULT_FEE_TIER
+				),
+				Error::<Test>::AssetLimitReached
+			);
+		});
+}
+
+#[test]
+fn create_pool_fails_when_too_many_lp_asset_ids_are_taken() {
+	let asset_a: AssetId = 1001;
+	let asset_b: AssetId = 1002;
+	let first_id: AssetId = 10000;
+	let taken_ids: Vec<_> = (first_id..first_id + MaxLpAssetIdProbes::get())
+		.map(|taken_id| (taken_id, BOB, 1))
+		.collect();
+
+	ExtBuilder::default()
+		.with_endowed_balances([vec![(asset_a, ALICE, 1), (asset_b, ALICE, 1)], taken_ids].concat())
+		.build_and_execute(|| {
+			assert_noop!(
+				Dex::create_pool(
+					RuntimeOrigin::signed(ALICE),
+					asset_a.into(),
+					asset_b.into(),
+					DEFAULT_FEE_TIER
+				),
+				Error::<Test>::AssetLimitReached
+			);
+		});
+}
+
+#[test]
+fn taken_lp_asset_ids_are_skipped_when_the_block_is_idle() {
+	let asset_a: AssetId = 1001;
+	let asset_b: AssetId = 1002;
+	let first_id: AssetId = 10000;
+	let last_taken_id = first_id + MaxLpAssetIdProbes::get();
+	let taken_ids: Vec<_> = (first_id..=last_taken_id).map(|taken_id| (taken_id, BOB, 1)).collect();
+
+	ExtBuilder::default()
+		.with_endowed_balances([vec![(asset_a, ALICE, 1), (asset_b, ALICE, 1)], taken_ids].concat())
+		.build_and_execute(|| {
+			Dex::on_idle(System::block_number(), Weight::MAX);
+			assert_eq!(NextLpAssetId::<Test>::get(), Some(last_taken_id + 1));
+
+			assert_ok!(Dex::create_pool(
+				RuntimeOrigin::signed(ALICE),
+				asset_a.into(),
+				asset_b.into(),
+				DEFAULT_FEE_TIER
+			));
+			let pool_key = AssetPair::new(asset_a.into(), asset_b.into());
+			assert_eq!(LiquidityPools::<Test>::get(pool_key).unwrap().id, last_taken_id + 1);
+		});
+}
+
+#[test]
+fn assets_cannot_be_created_with_lp_asset_ids() {
+	ExtBuilder::default().build_and_execute(|| {
+		for lp_asset_id in [MinLpAssetId::get(), MaxLpAssetId::get()] {
+			assert_noop!(
+				Assets::create(RuntimeOrigin::signed(ALICE), lp_asset_id.into(), ALICE, 1),
+				DispatchError::BadOrigin
+			);
+		}
+		assert_ok!(Assets::create(
+			RuntimeOrigin::signed(ALICE),
+			(MinLpAssetId::get() - 1).into(),
+			ALICE,
+			1
+		));
+	});
+}
+
+#[test]
+fn destroy_pool_releases_the_deposit_of_an_empty_pool() {
+	let asset_a: AssetId = 1001;
+	let asset_b: AssetId = 1002;
+
+	ExtBuilder::default()
+		.with_endowed_balances(vec![(asset_a, ALICE, 1), (asset_b, ALICE, 1)])
+		.build_and_execute(|| {
+			assert_ok!(Dex::create_pool(
+				RuntimeOrigin::signed(ALICE),
+				asset_a.into(),
+				asset_b.into(),
+				DEFAULT_FEE_TIER
+			));
+			let pool_key = AssetPair::new(asset_a.into(), asset_b.into());
+			let pool = LiquidityPools::<Test>::get(&pool_key).unwrap();
+
+			assert_ok!(Dex::destroy_pool(
+				RuntimeOrigin::signed(ALICE),
+				asset_a.into(),
+				asset_b.into()
+			));
+
+			assert!(!LiquidityPools::<Test>::contains_key(&pool_key));
+			assert_eq!(Balances::balance_on_hold(&HoldReason::PoolDeposit.into(), &ALICE), 0);
+			// Only the existential deposit of the pool account is kept
+			assert_eq!(Balances::free_balance(ALICE), POOL_DEPOSIT * 10 - EXISTENTIAL_DEPOSIT);
+			frame_system::Pallet::<Test>::assert_last_event(RuntimeEvent::Dex(
+				Event::LiquidityPoolDestroyed {
+					who: ALICE,
+					pool_id: pool.id,
+					asset_a: asset_a.into(),
+					asset_b: asset_b.into(),
+				},
+			));
+
+			// The pair can get a new pool
+			assert_ok!(Dex::create_pool(
+				RuntimeOrigin::signed(ALICE),
+				asset_a.into(),
+				asset_b.into(),
+				DEFAULT_FEE_TIER
+			));
+		});
+}
+
+#[test]
+fn destroy_pool_fails_for_other_accounts_and_pools_with_liquidity() {
+	let asset_a: AssetId = 1001;
+	let asset_b: AssetId = 1002;
+	let amount: u128 = expand_to_decimals(10u128);
+
+	ExtBuilder::default()
+		.with_endowed_balances(vec![(asset_a, ALICE, amount), (asset_b, ALICE, amount)])
+		.build_and_execute(|| {
+			assert_noop!(
+				Dex::destroy_pool(RuntimeOrigin::signed(ALICE), asset_a.into(), asset_b.into()),
+				Error::<Test>::LiquidityPoolDoesNotExist
+			);
+
+			assert_ok!(Dex::create_pool(
+				RuntimeOrigin::signed(ALICE),
+				asset_a.into(),
+				asset_b.into(),
+				DEFAULT_FEE_TIER
+			));
+			assert_noop!(
+				Dex::destroy_pool(RuntimeOrigin::signed(BOB), asset_a.into(), asset_b.into()),
+				Error::<Test>::NotPoolDepositor
+			);
+
+			assert_ok!(Dex::mint(
+				RuntimeOrigin::signed(ALICE),
+				asset_a.into(),
+				asset_b.into(),
+				amount,
+				amount,
+				0,
+				0,
+				None
+			));
+			assert_noop!(
+				Dex::destroy_pool(RuntimeOrigin::signed(ALICE), asset_a.into(), asset_b.into()),
+				Error::<Test>::PoolNotEmpty
+			);
+		});
+}
@@ -0,0 +1,250 @@
+use crate::tests::mock::*;
+use crate::types::AssetPair;
+use crate::{Error, Event, FlashSwapLock, LiquidityPools};
+use frame_support::traits::fungibles::Mutate;
+use frame_support::traits::tokens::Preservation;
+use frame_support::{assert_noop, assert_ok};
+
+#[test]
+fn flash_swap_works_when_repaid_in_other_asset() {
+	let asset_a: AssetId = 1001;
+	let asset_b: AssetId = 1002;
+	let total: u128 = expand_to_decimals(100u128);
+	let amount: u128 = expand_to_decimals(10u128);
+
+	ExtBuilder::default()
+		.with_endowed_balances(vec![(asset_a, ALICE, total), (asset_b, ALICE, total)])
+		.build_and_execute(|| {
+			create_pool_with_liquidity(asset_a, asset_b, amount, amount);
+			let amount_out = expand_to_decimals(1u128);
+			// Same as the input of a swap for exactly `amount_out`
+			let amount_in: u128 = 11144544746;
+			set_flash_swap_action(FlashSwapAction::Repay(asset_a.into(), amount_in));
+
+			assert_ok!(Dex::flash_swap(
+				RuntimeOrigin::signed(ALICE),
+				asset_a.into(),
+				asset_b.into(),
+				0,
+				amount_out,
+				None
+			));
+
+			// Internal balances should be updated
+			let pool_key = AssetPair::new(asset_a.into(), asset_b.into());
+			let pool = LiquidityPools::<Test>::get(pool_key).unwrap();
+			assert_eq!(pool.asset_a_balance, amount + amount_in);
+			assert_eq!(pool.asset_b_balance, amount - amount_out);
+			assert!(!FlashSwapLock::<Test>::get());
+
+			// User balances have been updated
+			assert_eq!(Fungibles::balance(asset_a, ALICE), total - amount - amount_in);
+			assert_eq!(Fungibles::balance(asset_b, ALICE), total - amount + amount_out);
+
+			// Ensure correct events are triggered
+			frame_system::Pallet::<Test>::assert_has_event(RuntimeEvent::Dex(
+				Event::FlashSwapped {
+					who: ALICE,
+					pool_id: pool.id,
+					asset_a: asset_a.into(),
+					asset_b: asset_b.into(),
+					amount_a_out: 0,
+					amount_b_out: amount_out,
+					amount_a_in: amount_in,
+					amount_b_in: 0,
+					reserve_a: amount + amount_in,
+					reserve_b: amount - amount_out,
+				},
+			));
+		});
+}
+
+#[test]
+fn flash_swap_works_when_repaid_in_same_asset() {
+	let asset_a: AssetId = 1001;
+	let asset_b: AssetId = 1002;
+	let total: u128 = expand_to_decimals(100u128);
+	let amount: u128 = expand_to_decimals(10u128);
+
+	ExtBuilder::default()
+		.with_endowed_balances(vec![(asset_a, ALICE, total), (asset_b, ALICE, total)])
+		.build_and_execute(|| {
+			create_pool_with_liquidity(asset_a, asset_b, amount, amount);
+			let amount_out = expand_to_decimals(1u128);
+			// The borrowed amount plus the 0.3% fee on the repayment
+			let amount_in: u128 = 10030090271;
+			set_flash_swap_action(FlashSwapAction::Repay(asset_b.into(), amount_in));
+
+			assert_ok!(Dex::flash_swap(
+				RuntimeOrigin::signed(ALICE),
+				asset_a.into(),
+				asset_b.into(),
+				0,
+				amount_out,
+				None
+			));
+
+			let pool_key = AssetPair::new(asset_a.into(), asset_b.into());
+			let pool = LiquidityPools::<Test>::get(pool_key).unwrap();
+			assert_eq!(pool.asset_a_balance, amount);
+			assert_eq!(pool.asset_b_balance, amount - amount_out + amount_in);
+			assert_eq!(Fungibles::balance(asset_b, ALICE), total - amount + amount_out - amount_in);
+		});
+}
+
+#[test]
+fn flash_swap_counts_assets_sent_to_the_pool_beforehand() {
+	let asset_a: AssetId = 1001;
+	let asset_b: AssetId = 1002;
+	let total: u128 = expand_to_decimals(100u128);
+	let amount: u128 = expand_to_decimals(10u128);
+
+	ExtBuilder::default()
+		.with_endowed_balances(vec![(asset_a, ALICE, total), (asset_b, ALICE, total)])
+		.build_and_execute(|| {
+			create_pool_with_liquidity(asset_a, asset_b, amount, amount);
+			let amount_out = expand_to_decimals(1u128);
+			let amount_in: u128 = 10030090271;
+			let pool_key = AssetPair::new(asset_a.into(), asset_b.into());
+			let pool = LiquidityPools::<Test>::get(&pool_key).unwrap();
+			// Like in Uniswap V2, the balance above the reserves pays for the flash swap, even
+			// when it's sent before the callback
+			assert_ok!(Fungibles::transfer(
+				asset_b,
+				&ALICE,
+				&pool.manager,
+				amount_in,
+				Preservation::Expendable
+			));
+
+			assert_ok!(Dex::flash_swap(
+				RuntimeOrigin::signed(ALICE),
+				asset_a.into(),
+				asset_b.into(),
+				0,
+				amount_out,
+				None
+			));
+
+			let pool = LiquidityPools::<Test>::get(&pool_key).unwrap();
+			assert_eq!(pool.asset_b_balance, amount - amount_out + amount_in);
+		});
+}
+
+#[test]
+fn flash_swap_fails_when_not_repaid_enough() {
+	let asset_a: AssetId = 1001;
+	let asset_b: AssetId = 1002;
+	let total: u128 = expand_to_decimals(100u128);
+	let amount: u128 = expand_to_decimals(10u128);
+
+	ExtBuilder::default()
+		.with_endowed_balances(vec![(asset_a, ALICE, total), (asset_b, ALICE, total)])
+		.build_and_execute(|| {
+			create_pool_with_liquidity(asset_a, asset_b, amount, amount);
+			let amount_out = expand_to_decimals(1u128);
+			set_flash_swap_action(FlashSwapAction::Repay(asset_b.into(), 10030090270));
+
+			assert_noop!(
+				Dex::flash_swap(
+					RuntimeOrigin::signed(ALICE),
+					asset_a.into(),
+					asset_b.into(),
+					0,
+					amount_out,
+					None
+				),
+				Error::<Test>::InvariantViolated
+			);
+		});
+}
+
+#[test]
+fn flash_swap_fails_when_not_repaid() {
+	let asset_a: AssetId = 1001;
+	let asset_b: AssetId = 1002;
+	let total: u128 = expand_to_decimals(100u128);
+	let amount: u128 = expand_to_decimals(10u128);
+
+	ExtBuilder::default()
+		.with_endowed_balances(vec![(asset_a, ALICE, total), (asset_b, ALICE, total)])
+		.build_and_execute(|| {
+			create_pool_with_liquidity(asset_a, asset_b, amount, amount);
+
+			assert_noop!(
+				Dex::flash_swap(
+					RuntimeOrigin::signed(ALICE),
+					asset_a.into(),
+					asset_b.into(),
+					expand_to_decimals(1u128),
+					0,
+					None
+				),
+				Error::<Test>::InsufficientInputAmount
+			);
+		});
+}
+
+#[test]
+fn flash_swap_fails_when_callback_uses_the_pools() {
+	let asset_a: AssetId = 1001;
+	let asset_b: AssetId = 1002;
+	let total: u128 = expand_to_decimals(100u128);
+	let amount: u128 = expand_to_decimals(10u128);
+
+	ExtBuilder::default()
+		.with_endowed_balances(vec![(asset_a, ALICE, total), (asset_b, ALICE, total)])
+		.build_and_execute(|| {
+			create_pool_with_liquidity(asset_a, asset_b, amount, amount);
+			set_flash_swap_action(FlashSwapAction::Swap(expand_to_decimals(1u128)));
+
+			assert_noop!(
+				Dex::flash_swap(
+					RuntimeOrigin::signed(ALICE),
+					asset_a.into(),
+					asset_b.into(),
+					0,
+					expand_to_decimals(1u128),
+					None
+				),
+				Error::<Test>::FlashSwapInProgress
+			);
+		});
+}
+
+#[test]
+fn flash_swap_fails_on_invalid_output_amounts() {
+	let asset_a: AssetId = 1001;
+	let asset_b: AssetId = 1002;
+	let total: u128 = expand_to_decimals(100u128);
+	let amount: u128 = expand_to_decimals(10u128);
+
+	ExtBuilder::default()
+		.with_endowed_balances(vec![(asset_a, ALICE, total), (asset_b, ALICE, total)])
+		.build_and_execute(|| {
+			create_pool_with_liquidity(asset_a, asset_b, amount, amount);
+
+			assert_noop!(
+				Dex::flash_swap(
+					RuntimeOrigin::signed(ALICE),
+					asset_a.into(),
+					asset_b.into(),
+					0,
+					0,
+					None
+				),
+				Error::<Test>::InsufficientOutputAmount
+			);
+			assert_noop!(
+				Dex::flash_swap(
+					RuntimeOrigin::signed(ALICE),
+					asset_a.into(),
+					asset_b.into(),
+					0,
+					amount,
+					None
+				),
+				Error::<Test>::InsufficientLiquidity
+			);
+		});
+}
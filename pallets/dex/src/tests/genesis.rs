@@ -0,0 +1,136 @@
+use crate::tests::mock::*;
+use crate::types::AssetPair;
+use crate::{LiquidityPools, NativeOrAssetId, NextLpAssetId};
+use frame_support::assert_ok;
+use sp_runtime::Permill;
+
+#[test]
+fn genesis_creates_pools_with_liquidity() {
+	let asset_a: AssetId = 1001;
+	let asset_b: AssetId = 1002;
+	let total: u128 = expand_to_decimals(100u128);
+	let amount: u128 = expand_to_decimals(10u128);
+	let amount_native: u128 = expand_to_decimals(20u128);
+
+	ExtBuilder::default()
+		.with_endowed_balances(vec![(asset_a, ALICE, total), (asset_b, ALICE, total)])
+		.with_native_balances(vec![(ALICE, total)])
+		.with_pools(vec![
+			(10_005, asset_a.into(), asset_b.into(), amount, amount, ALICE),
+			(10_006, NativeOrAssetId::Native, asset_a.into(), amount_native, amount, ALICE),
+		])
+		.build_and_execute(|| {
+			let pool = LiquidityPools::<Test>::get(AssetPair::new(asset_a.into(), asset_b.into()))
+				.unwrap();
+			assert_eq!(pool.id, 10_005);
+			assert_eq!(pool.manager, Dex::pool_account(10_005));
+			assert_eq!(pool.asset_a_balance, amount);
+			assert_eq!(pool.asset_b_balance, amount);
+			// Genesis pools get the first fee tier
+			assert_eq!(pool.fee, Permill::from_parts(500));
+			assert_eq!(pool.depositor, ALICE);
+			assert_eq!(pool.deposit, POOL_DEPOSIT);
+			assert_eq!(pool.block_timestamp_last, 0);
+			assert_eq!(Fungibles::balance(10_005, ALICE), amount - MIN_LIQUIDITY);
+
+			let native_pool = LiquidityPools::<Test>::get(AssetPair::new(
+				NativeOrAssetId::Native,
+				asset_a.into(),
+			))
+			.unwrap();
+			assert_eq!(native_pool.id, 10_006);
+			assert_eq!(
+				Balances::free_balance(native_pool.manager),
+				amount_native + EXISTENTIAL_DEPOSIT
+			);
+			assert_eq!(
+				Balances::free_balance(ALICE),
+				total - amount_native - (POOL_DEPOSIT + EXISTENTIAL_DEPOSIT) * 2
+			);
+			assert_eq!(Fungibles::balance(asset_a, ALICE), total - amount * 2);
+			// Creating the pools moves the native currency around without minting any
+			assert_eq!(Balances::total_issuance(), total);
+
+			// The genesis pools work like any other
+			assert_ok!(Dex::swap(
+				RuntimeOrigin::signed(ALICE),
+				asset_a.into(),
+				asset_b.into(),
+				expand_to_decimals(1u128),
+				None
+			));
+		});
+}
+
+#[test]
+fn genesis_pools_move_the_next_lp_asset_id_past_their_lp_tokens() {
+	let asset_a: AssetId = 1001;
+	let other_assets: Vec<AssetId> = (1002..1008).collect();
+	let total: u128 = expand_to_decimals(100u128);
+	let amount: u128 = expand_to_decimals(10u128);
+
+	// More genesis pools than `create_pool` may probe taken ids for
+	let pools: Vec<GenesisPool> = other_assets[..5]
+		.iter()
+		.zip(MinLpAssetId::get()..)
+		.map(|(asset, lp_asset_id)| {
+			(lp_asset_id, asset_a.into(), (*asset).into(), amount, amount, ALICE)
+		})
+		.collect();
+	assert!(pools.len() as u32 > MaxLpAssetIdProbes::get());
+	let mut balances = vec![(asset_a, ALICE, total)];
+	balances.extend(other_assets.iter().map(|asset| (*asset, ALICE, total)));
+
+	ExtBuilder::default().with_endowed_balances(balances).build_and_execute(|| {
+		let next_lp_asset_id = MinLpAssetId::get() + 5;
+		assert_eq!(NextLpAssetId::<Test>::get(), Some(next_lp_asset_id));
+
+		// Pools created later get the first id after the genesis pools
+		assert_ok!(Dex::create_pool(
+			RuntimeOrigin::signed(ALICE),
+			asset_a.into(),
+			other_assets[5].into(),
+			DEFAULT_FEE_TIER
+		));
+		let pool =
+			LiquidityPools::<Test>::get(AssetPair::new(asset_a.into(), other_assets[5].into()))
+				.unwrap();
+		assert_eq!(pool.id, next_lp_asset_id);
+	});
+}
+
+#[test]
+fn genesis_does_not_mint_native_currency() {
+	ExtBuilder::default().build_and_execute(|| {
+		// Only the endowed accounts hold the native currency
+		assert_eq!(Balances::total_issuance(), POOL_DEPOSIT * 20);
+	});
+}
+
+#[test]
+#[should_panic(
+	expected = "The LP token of a genesis pool is outside of the range reserved for LP tokens"
+)]
+fn genesis_fails_for_lp_token_outside_of_the_lp_range() {
+	let asset_a: AssetId = 1001;
+	let asset_b: AssetId = 1002;
+	let amount: u128 = expand_to_decimals(10u128);
+
+	ExtBuilder::default()
+		.with_endowed_balances(vec![(asset_a, ALICE, amount), (asset_b, ALICE, amount)])
+		.with_pools(vec![(1003, asset_a.into(), asset_b.into(), amount, amount, ALICE)])
+		.build();
+}
+
+#[test]
+#[should_panic(expected = "Genesis pool providers hold the initial liquidity")]
+fn genesis_fails_for_provider_without_liquidity() {
+	let asset_a: AssetId = 1001;
+	let asset_b: AssetId = 1002;
+	let amount: u128 = expand_to_decimals(10u128);
+
+	ExtBuilder::default()
+		.with_endowed_balances(vec![(asset_a, ALICE, amount), (asset_b, BOB, amount)])
+		.with_pools(vec![(10_000, asset_a.into(), asset_b.into(), amount, amount, ALICE)])
+		.build();
+}
@@ -0,0 +1,247 @@
+use crate::migrations::{v1, v1::v0, v2};
+use crate::tests::mock::*;
+use crate::types::AssetPair;
+use crate::{LiquidityPools, NativeOrAssetAdapter, NativeOrAssetId};
+use frame_support::assert_ok;
+use frame_support::traits::fungibles::Mutate;
+use frame_support::traits::{
+	tokens::Preservation, GetStorageVersion, OnRuntimeUpgrade, StorageVersion,
+};
+use sp_runtime::traits::AccountIdConversion;
+use sp_runtime::{FixedU128, Permill};
+
+/// Creates a pool like the first release did: stored in the old layout and with its LP token
+/// and funds in the pallet account, which also holds some native currency like it got at genesis.
+fn create_v0_pool(asset_a: AssetId, asset_b: AssetId, lp_asset_id: AssetId, amount: u128) {
+	let pallet_account: AccountId = MemeSwapPallet::get().into_account_truncating();
+	assert_ok!(NativeOrAssetAdapter::<Test>::transfer(
+		NativeOrAssetId::Native,
+		&ALICE,
+		&pallet_account,
+		POOL_DEPOSIT,
+		Preservation::Expendable
+	));
+	assert_ok!(Assets::force_create(
+		RuntimeOrigin::root(),
+		lp_asset_id.into(),
+		pallet_account,
+		true,
+		1
+	));
+	assert_ok!(Fungibles::mint_into(lp_asset_id, &pallet_account, MIN_LIQUIDITY));
+	assert_ok!(Fungibles::mint_into(lp_asset_id, &ALICE, amount - MIN_LIQUIDITY));
+	for asset in [asset_a, asset_b] {
+		assert_ok!(NativeOrAssetAdapter::<Test>::transfer(
+			asset.into(),
+			&ALICE,
+			&pallet_account,
+			amount,
+			Preservation::Expendable
+		));
+	}
+	v0::LiquidityPools::<Test>::insert(
+		v0::AssetPair { asset_a, asset_b },
+		v0::LiquidityPool {
+			id: lp_asset_id,
+			manager: pallet_account,
+			asset_a_balance: amount,
+			asset_b_balance: amount,
+		},
+	);
+}
+
+#[test]
+fn migration_to_v1_translates_v0_pools() {
+	let asset_a: AssetId = 1001;
+	let asset_b: AssetId = 1002;
+	let total: u128 = expand_to_decimals(100u128);
+	let amount: u128 = expand_to_decimals(10u128);
+
+	ExtBuilder::default()
+		.with_endowed_balances(vec![(asset_a, ALICE, total), (asset_b, ALICE, total)])
+		.build_and_execute(|| {
+			create_v0_pool(asset_a, asset_b, 10_000, amount);
+			advance_time(OBSERVATION_PERIOD);
+
+			v1::MigrateToV1::<Test>::on_runtime_upgrade();
+
+			assert_eq!(Dex::on_chain_storage_version(), 1);
+			let pallet_account: AccountId = MemeSwapPallet::get().into_account_truncating();
+			let pool = LiquidityPools::<Test>::get(AssetPair::new(asset_a.into(), asset_b.into()))
+				.unwrap();
+			assert_eq!(pool.id, 10_000);
+			assert_eq!(pool.manager, pallet_account);
+			assert_eq!(pool.asset_a_balance, amount);
+			assert_eq!(pool.asset_b_balance, amount);
+			assert_eq!(pool.fee, Permill::from_parts(3_000));
+			assert_eq!(pool.k_last, 0);
+			assert_eq!(pool.depositor, pallet_account);
+			assert_eq!(pool.deposit, 0);
+			assert_eq!(pool.price_a_cumulative_last, FixedU128::from_inner(0));
+			assert_eq!(pool.block_timestamp_last, OBSERVATION_PERIOD);
+			assert_eq!(LiquidityPools::<Test>::iter().count(), 1);
+		});
+}
+
+#[test]
+fn migration_to_v2_moves_pool_funds_to_the_pool_account() {
+	let asset_a: AssetId = 1001;
+	let asset_b: AssetId = 1002;
+	let total: u128 = expand_to_decimals(100u128);
+	let amount: u128 = expand_to_decimals(10u128);
+
+	ExtBuilder::default()
+		.with_endowed_balances(vec![(asset_a, ALICE, total), (asset_b, ALICE, total)])
+		.build_and_execute(|| {
+			create_v0_pool(asset_a, asset_b, 10_000, amount);
+			let pallet_account: AccountId = MemeSwapPallet::get().into_account_truncating();
+			let total_issuance = Balances::total_issuance();
+
+			v1::MigrateToV1::<Test>::on_runtime_upgrade();
+			v2::MigrateToV2::<Test>::on_runtime_upgrade();
+
+			assert_eq!(Dex::on_chain_storage_version(), 2);
+			let pool = LiquidityPools::<Test>::get(AssetPair::new(asset_a.into(), asset_b.into()))
+				.unwrap();
+			assert_eq!(pool.manager, Dex::pool_account(pool.id));
+			assert_eq!(Fungibles::balance(asset_a, pool.manager), amount);
+			assert_eq!(Fungibles::balance(asset_b, pool.manager), amount);
+			assert_eq!(Fungibles::balance(pool.id, pool.manager), MIN_LIQUIDITY);
+			assert_eq!(Fungibles::balance(asset_a, pallet_account), 0);
+			assert_eq!(Fungibles::balance(asset_b, pallet_account), 0);
+			assert_eq!(Fungibles::balance(pool.id, pallet_account), 0);
+			// The existential deposit of the pool account is paid by the pallet account
+			assert_eq!(Balances::free_balance(pool.manager), EXISTENTIAL_DEPOSIT);
+			assert_eq!(Balances::free_balance(pallet_account), POOL_DEPOSIT - EXISTENTIAL_DEPOSIT);
+			assert_eq!(Balances::total_issuance(), total_issuance);
+
+			// The migrated pool works like any other
+			assert_ok!(Dex::burn(
+				RuntimeOrigin::signed(ALICE),
+				asset_a.into(),
+				asset_b.into(),
+				amount - MIN_LIQUIDITY,
+				0,
+				0,
+				None
+			));
+			assert_eq!(Fungibles::balance(asset_a, ALICE), total);
+			assert!(!LiquidityPools::<Test>::contains_key(AssetPair::new(
+				asset_a.into(),
+				asset_b.into()
+			)));
+		});
+}
+
+#[test]
+fn migration_to_v2_splits_shared_balances_between_pools_pro_rata() {
+	let shared_asset: AssetId = 1001;
+	let asset_b: AssetId = 1002;
+	let asset_c: AssetId = 1003;
+	let total: u128 = expand_to_decimals(100u128);
+	let amount: u128 = expand_to_decimals(10u128);
+	let donation: u128 = expand_to_decimals(1u128);
+
+	ExtBuilder::default()
+		.with_endowed_balances(vec![
+			(shared_asset, ALICE, total),
+			(asset_b, ALICE, total),
+			(asset_c, ALICE, total),
+			(shared_asset, BOB, total),
+			(asset_b, BOB, total),
+		])
+		.build_and_execute(|| {
+			create_v0_pool(shared_asset, asset_b, 10_000, amount);
+			create_v0_pool(shared_asset, asset_c, 10_001, amount);
+			let pallet_account: AccountId = MemeSwapPallet::get().into_account_truncating();
+			let pool_b_key = AssetPair::new(shared_asset.into(), asset_b.into());
+			let pool_c_key = AssetPair::new(shared_asset.into(), asset_c.into());
+
+			v1::MigrateToV1::<Test>::on_runtime_upgrade();
+			// Trade in one of the pools and send the shared account some of the shared asset, so
+			// its balance no longer matches the reserves of either pool
+			assert_ok!(Dex::swap(
+				RuntimeOrigin::signed(BOB),
+				asset_b.into(),
+				shared_asset.into(),
+				expand_to_decimals(2u128),
+				None
+			));
+			assert_ok!(Fungibles::transfer(
+				shared_asset,
+				&BOB,
+				&pallet_account,
+				donation,
+				Preservation::Expendable
+			));
+			let pool_b = LiquidityPools::<Test>::get(&pool_b_key).unwrap();
+			let pool_c = LiquidityPools::<Test>::get(&pool_c_key).unwrap();
+			let shared_balance = Fungibles::balance(shared_asset, pallet_account);
+			let shared_reserves = pool_b.asset_a_balance + pool_c.asset_a_balance;
+			assert_eq!(shared_balance, shared_reserves + donation);
+			let total_issuance = Balances::total_issuance();
+
+			v2::MigrateToV2::<Test>::on_runtime_upgrade();
+
+			// Whichever pool is migrated last also gets what was rounded down for the other
+			let share_b = shared_balance * pool_b.asset_a_balance / shared_reserves;
+			let migrated_b = LiquidityPools::<Test>::get(&pool_b_key).unwrap();
+			let migrated_c = LiquidityPools::<Test>::get(&pool_c_key).unwrap();
+			assert!(migrated_b.asset_a_balance.abs_diff(share_b) <= 1);
+			assert_eq!(migrated_b.asset_a_balance + migrated_c.asset_a_balance, shared_balance);
+			assert_eq!(migrated_b.asset_b_balance, pool_b.asset_b_balance);
+			assert_eq!(migrated_c.asset_b_balance, pool_c.asset_b_balance);
+			assert_eq!(
+				Fungibles::balance(shared_asset, migrated_b.manager),
+				migrated_b.asset_a_balance
+			);
+			assert_eq!(
+				Fungibles::balance(shared_asset, migrated_c.manager),
+				migrated_c.asset_a_balance
+			);
+			assert_eq!(Fungibles::balance(shared_asset, pallet_account), 0);
+			// The pool accounts are kept alive by their existential deposit like new ones
+			assert_eq!(Balances::free_balance(migrated_b.manager), EXISTENTIAL_DEPOSIT);
+			assert_eq!(Balances::free_balance(migrated_c.manager), EXISTENTIAL_DEPOSIT);
+			assert_eq!(Balances::total_issuance(), total_issuance);
+
+			// Both pools can be left completely
+			for asset in [asset_b, asset_c] {
+				assert_ok!(Dex::burn(
+					RuntimeOrigin::signed(ALICE),
+					shared_asset.into(),
+					asset.into(),
+					amount - MIN_LIQUIDITY,
+					0,
+					0,
+					None
+				));
+			}
+			assert_eq!(LiquidityPools::<Test>::iter().count(), 0);
+		});
+}
+
+#[test]
+fn migrations_are_skipped_on_other_storage_versions() {
+	let asset_a: AssetId = 1001;
+	let asset_b: AssetId = 1002;
+	let total: u128 = expand_to_decimals(100u128);
+	let amount: u128 = expand_to_decimals(10u128);
+
+	ExtBuilder::default()
+		.with_endowed_balances(vec![(asset_a, ALICE, total), (asset_b, ALICE, total)])
+		.build_and_execute(|| {
+			StorageVersion::new(2).put::<Dex>();
+			create_pool_with_liquidity(asset_a, asset_b, amount, amount);
+			let pool_key = AssetPair::new(asset_a.into(), asset_b.into());
+			let pool = LiquidityPools::<Test>::get(&pool_key).unwrap();
+
+			v1::MigrateToV1::<Test>::on_runtime_upgrade();
+			v2::MigrateToV2::<Test>::on_runtime_upgrade();
+
+			assert_eq!(Dex::on_chain_storage_version(), 2);
+			assert_eq!(LiquidityPools::<Test>::get(&pool_key).unwrap(), pool);
+			assert_eq!(Fungibles::balance(asset_a, pool.manager), amount);
+			assert_eq!(Fungibles::balance(asset_b, pool.manager), amount);
+		});
+}
@@ -1,11 +1,15 @@
 use crate as pallet_dex;
+use crate::traits::FlashSwapCallback;
+use crate::{NativeOrAssetAdapter, NativeOrAssetId};
+use frame_support::traits::tokens::Preservation;
 use frame_support::traits::{AsEnsureOriginWithArg, ConstU128, ConstU16, ConstU32, ConstU64};
-use frame_support::{parameter_types, PalletId};
+use frame_support::weights::Weight;
+use frame_support::{assert_ok, parameter_types, PalletId};
 use frame_system::{EnsureRoot, EnsureSigned};
 use sp_core::{sp_std, H256};
 use sp_runtime::{
 	traits::{BlakeTwo256, IdentityLookup},
-	BuildStorage, FixedU128,
+	BuildStorage, DispatchResult, FixedU128, Permill,
 };
 use sp_std::prelude::*;
 use std::cell::RefCell;
@@ -13,21 +17,79 @@ use std::cell::RefCell;
 type Block = frame_system::mocking::MockBlock<Test>;
 pub type Balance = u128;
 pub type AssetId = u32;
-pub type AccountId = u64;
+// Wide enough for the pool accounts, which are sub accounts of the pallet account
+pub type AccountId = u128;
 pub const MIN_LIQUIDITY: u128 = 1000;
+pub const DEFAULT_FEE_TIER: Permill = Permill::from_parts(3_000);
+pub const POOL_DEPOSIT: Balance = 100;
+pub const EXISTENTIAL_DEPOSIT: Balance = 1;
+pub const OBSERVATION_PERIOD: u64 = 60;
 pub type Fungibles = <Test as crate::Config>::Fungibles;
 
 pub const ADMIN: AccountId = 1;
 pub const ALICE: AccountId = 2;
+pub const BOB: AccountId = 3;
 
 parameter_types! {
 	pub const MemeSwapPallet: PalletId = PalletId(*b"MeMeSwap");
 	pub const TokenDecimals: u32 = 10;
 	pub const MinimumLiquidity: u32 = 1000;
+	pub const MaxHops: u32 = 4;
+	pub const MaxPoolsScanned: u32 = 2;
+	pub const MinLpAssetId: AssetId = 10_000;
+	pub const MaxLpAssetId: AssetId = 19_999;
+	pub const MaxLpAssetIdProbes: u32 = 4;
+	pub SwapFeeTiers: Vec<Permill> =
+		vec![Permill::from_parts(500), DEFAULT_FEE_TIER, Permill::from_parts(10_000)];
+	pub const PoolDeposit: Balance = POOL_DEPOSIT;
+	pub const MaxObservations: u32 = 4;
+	pub const ObservationPeriod: u64 = OBSERVATION_PERIOD;
 }
 
 thread_local! {
 	pub static ENDOWED_BALANCES: RefCell<Vec<(AssetId, AccountId, Balance)>> = RefCell::new(Vec::new());
+	pub static FLASH_SWAP_ACTION: RefCell<FlashSwapAction> = RefCell::new(FlashSwapAction::Nothing);
+}
+
+/// What `MockFlashSwapCallback` does when it's called.
+#[derive(Clone, Copy)]
+pub enum FlashSwapAction {
+	Nothing,
+	/// Pays the pool back with an amount of an asset
+	Repay(NativeOrAssetId<AssetId>, Balance),
+	/// Tries to swap in the pool before paying it back
+	Swap(Balance),
+}
+
+pub struct MockFlashSwapCallback;
+
+impl FlashSwapCallback<AccountId, NativeOrAssetId<AssetId>, Balance> for MockFlashSwapCallback {
+	fn on_flash_swap(
+		who: &AccountId,
+		pool_account: &AccountId,
+		asset_a: NativeOrAssetId<AssetId>,
+		asset_b: NativeOrAssetId<AssetId>,
+		_amount_a_out: Balance,
+		_amount_b_out: Balance,
+	) -> DispatchResult {
+		match FLASH_SWAP_ACTION.with(|action| *action.borrow()) {
+			FlashSwapAction::Nothing => Ok(()),
+			FlashSwapAction::Repay(asset, amount) => NativeOrAssetAdapter::<Test>::transfer(
+				asset,
+				who,
+				pool_account,
+				amount,
+				Preservation::Expendable,
+			)
+			.map(|_| ()),
+			FlashSwapAction::Swap(amount) =>
+				Dex::swap(RuntimeOrigin::signed(*who), asset_a, asset_b, amount, None),
+		}
+	}
+
+	fn weight() -> Weight {
+		Weight::zero()
+	}
 }
 
 // Configure a mock runtime to test the pallet.
@@ -35,6 +97,7 @@ frame_support::construct_runtime!(
 	pub enum Test
 	{
 		System: frame_system,
+		Timestamp: pallet_timestamp,
 		Balances: pallet_balances,
 		Assets: pallet_assets,
 		Dex: pallet_dex,
@@ -51,7 +114,7 @@ impl frame_system::Config for Test {
 	type Nonce = u64;
 	type Hash = H256;
 	type Hashing = BlakeTwo256;
-	type AccountId = u64;
+	type AccountId = AccountId;
 	type Lookup = IdentityLookup<Self::AccountId>;
 	type Block = Block;
 	type BlockHashCount = ConstU64<250>;
@@ -67,15 +130,22 @@ impl frame_system::Config for Test {
 	type MaxConsumers = frame_support::traits::ConstU32<16>;
 }
 
+impl pallet_timestamp::Config for Test {
+	type Moment = u64;
+	type OnTimestampSet = ();
+	type MinimumPeriod = ConstU64<1>;
+	type WeightInfo = ();
+}
+
 impl pallet_balances::Config for Test {
 	type RuntimeEvent = RuntimeEvent;
 	type WeightInfo = ();
 	type Balance = Balance;
 	type DustRemoval = ();
-	type ExistentialDeposit = ConstU128<1>;
+	type ExistentialDeposit = ConstU128<EXISTENTIAL_DEPOSIT>;
 	type AccountStore = System;
 	type ReserveIdentifier = [u8; 8];
-	type RuntimeHoldReason = ();
+	type RuntimeHoldReason = RuntimeHoldReason;
 	type FreezeIdentifier = ();
 	type MaxLocks = ConstU32<10>;
 	type MaxReserves = ();
@@ -90,7 +160,8 @@ impl pallet_assets::Config for Test {
 	type AssetId = u32;
 	type AssetIdParameter = codec::Compact<u32>;
 	type Currency = Balances;
-	type CreateOrigin = AsEnsureOriginWithArg<EnsureSigned<Self::AccountId>>;
+	type CreateOrigin =
+		pallet_dex::EnsureNotLpAssetId<Test, AsEnsureOriginWithArg<EnsureSigned<Self::AccountId>>>;
 	type ForceOrigin = EnsureRoot<Self::AccountId>;
 	type AssetDeposit = ConstU128<100>;
 	type AssetAccountDeposit = ConstU128<1>;
@@ -108,15 +179,35 @@ impl pallet_assets::Config for Test {
 
 impl pallet_dex::Config for Test {
 	type RuntimeEvent = RuntimeEvent;
+	type RuntimeHoldReason = RuntimeHoldReason;
 	type NativeBalance = Balances;
 	type Fungibles = Assets;
 	type PalletId = MemeSwapPallet;
 	type TokenDecimals = TokenDecimals;
 	type MinimumLiquidity = MinimumLiquidity;
+	type MaxHops = MaxHops;
+	type MaxPoolsScanned = MaxPoolsScanned;
+	type MinLpAssetId = MinLpAssetId;
+	type MaxLpAssetId = MaxLpAssetId;
+	type MaxLpAssetIdProbes = MaxLpAssetIdProbes;
+	type SwapFeeTiers = SwapFeeTiers;
+	type PoolDeposit = PoolDeposit;
+	type Time = Timestamp;
+	type MaxObservations = MaxObservations;
+	type ObservationPeriod = ObservationPeriod;
+	type FlashSwapCallback = MockFlashSwapCallback;
+	type AdminOrigin = EnsureRoot<AccountId>;
+	type WeightInfo = ();
 }
 
+/// A pool created at genesis: `(lp_asset_id, asset_a, asset_b, amount_a, amount_b, provider)`
+pub type GenesisPool =
+	(AssetId, NativeOrAssetId<AssetId>, NativeOrAssetId<AssetId>, Balance, Balance, AccountId);
+
 pub struct ExtBuilder {
 	endowed_balances: Vec<(AssetId, AccountId, Balance)>,
+	native_balances: Vec<(AccountId, Balance)>,
+	pools: Vec<GenesisPool>,
 }
 
 impl Default for ExtBuilder {
@@ -124,7 +215,13 @@ impl Default for ExtBuilder {
 		ENDOWED_BALANCES.with(|v| {
 			v.borrow_mut().clear();
 		});
-		Self { endowed_balances: vec![] }
+		FLASH_SWAP_ACTION.with(|action| *action.borrow_mut() = FlashSwapAction::Nothing);
+		Self {
+			endowed_balances: vec![],
+			// Enough for the pool creators to pay a few creation deposits
+			native_balances: vec![(ALICE, POOL_DEPOSIT * 10), (BOB, POOL_DEPOSIT * 10)],
+			pools: vec![],
+		}
 	}
 }
 
@@ -134,8 +231,22 @@ impl ExtBuilder {
 		self
 	}
 
+	pub fn with_native_balances(mut self, balances: Vec<(AccountId, Balance)>) -> Self {
+		self.native_balances = balances;
+		self
+	}
+
+	pub fn with_pools(mut self, pools: Vec<GenesisPool>) -> Self {
+		self.pools = pools;
+		self
+	}
+
 	pub fn build(self) -> sp_io::TestExternalities {
 		let mut t = frame_system::GenesisConfig::<Test>::default().build_storage().unwrap();
+		pallet_balances::GenesisConfig::<Test> { balances: self.native_balances }
+			.assimilate_storage(&mut t)
+			.unwrap();
+
 		let mut assets = vec![];
 		for (asset_id, _, _) in self.endowed_balances.clone().into_iter() {
 			assets.push((asset_id, ADMIN, true, 1));
@@ -149,16 +260,65 @@ impl ExtBuilder {
 		.assimilate_storage(&mut t)
 		.unwrap();
 
+		pallet_dex::GenesisConfig::<Test> { pools: self.pools }
+			.assimilate_storage(&mut t)
+			.unwrap();
+
 		let mut ext = sp_io::TestExternalities::new(t);
 		ext.execute_with(|| System::set_block_number(1));
 		ext
 	}
+
+	/// Builds the externalities and runs `test` in them, checking the pallet invariants after it.
+	pub fn build_and_execute(self, test: impl FnOnce()) {
+		self.build().execute_with(|| {
+			test();
+			Dex::do_try_state().unwrap();
+		})
+	}
+}
+
+/// Creates the pool of `asset_a` and `asset_b` and mints `amount_a` and `amount_b` into it, both
+/// as `ALICE`.
+pub(super) fn create_pool_with_liquidity(
+	asset_a: AssetId,
+	asset_b: AssetId,
+	amount_a: Balance,
+	amount_b: Balance,
+) {
+	assert_ok!(Dex::create_pool(
+		RuntimeOrigin::signed(ALICE),
+		asset_a.into(),
+		asset_b.into(),
+		DEFAULT_FEE_TIER
+	));
+	assert_ok!(Dex::mint(
+		RuntimeOrigin::signed(ALICE),
+		asset_a.into(),
+		asset_b.into(),
+		amount_a,
+		amount_b,
+		0,
+		0,
+		None
+	));
 }
 
 pub(super) fn expand_to_decimals(n: u128) -> u128 {
 	n * 10u128.pow(10u32)
 }
 
+/// Moves the chain to the next block, `seconds` after the current one.
+pub(super) fn advance_time(seconds: u64) {
+	System::set_block_number(System::block_number() + 1);
+	Timestamp::set_timestamp(Timestamp::get() + seconds * 1000);
+}
+
+/// Sets what the flash swap callback does in the following flash swaps.
+pub(super) fn set_flash_swap_action(action: FlashSwapAction) {
+	FLASH_SWAP_ACTION.with(|flash_swap_action| *flash_swap_action.borrow_mut() = action);
+}
+
 pub(super) fn decimals_to_numeric(n: u128) -> u128 {
 	FixedU128::from_inner(n).div(10u128.pow(10u32).into()).into_inner()
 }
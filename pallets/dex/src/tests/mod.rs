@@ -1,5 +1,15 @@
 mod add_liquidity;
+mod create_pool;
+mod flash_swap;
+mod genesis;
+mod migrations;
 pub(crate) mod mock;
+mod native_pool;
+mod pause;
+mod protocol_fee;
 mod remove_liquidity;
 mod swap;
+mod sync_skim;
 mod traits;
+mod try_state;
+mod twap;
@@ -0,0 +1,173 @@
+use crate::tests::mock::*;
+use crate::types::AssetPair;
+use crate::{Event, LiquidityPools, NativeOrAssetId};
+use frame_support::assert_ok;
+
+#[test]
+fn minting_native_pool_works() {
+	let asset_b: AssetId = 1002;
+	let pool_id: AssetId = 10000;
+	let amount_native: u128 = expand_to_decimals(10u128);
+	let amount_b: u128 = expand_to_decimals(50u128);
+
+	ExtBuilder::default()
+		.with_endowed_balances(vec![(asset_b, ALICE, amount_b)])
+		.with_native_balances(vec![(ALICE, amount_native * 2)])
+		.build_and_execute(|| {
+			assert_ok!(Dex::create_pool(
+				RuntimeOrigin::signed(ALICE),
+				NativeOrAssetId::Native,
+				asset_b.into(),
+				DEFAULT_FEE_TIER
+			));
+			assert_ok!(Dex::mint(
+				RuntimeOrigin::signed(ALICE),
+				NativeOrAssetId::Native,
+				asset_b.into(),
+				amount_native,
+				amount_b,
+				0,
+				0,
+				None
+			));
+
+			let pool_key = AssetPair::new(NativeOrAssetId::Native, asset_b.into());
+			let pool = LiquidityPools::<Test>::get(pool_key).unwrap();
+
+			assert_eq!(pool.asset_a_balance, amount_native);
+			assert_eq!(pool.asset_b_balance, amount_b);
+			// The creator pays the existential deposit of the pool account on top of the reserves
+			assert_eq!(Balances::free_balance(pool.manager), amount_native + EXISTENTIAL_DEPOSIT);
+			assert_eq!(
+				Balances::free_balance(ALICE),
+				amount_native - POOL_DEPOSIT - EXISTENTIAL_DEPOSIT
+			);
+			assert_eq!(Fungibles::balance(asset_b, pool.manager), amount_b);
+			assert_eq!(Fungibles::balance(pool_id, ALICE), 223606797749 - MIN_LIQUIDITY);
+
+			frame_system::Pallet::<Test>::assert_has_event(RuntimeEvent::Dex(
+				Event::LiquidityPoolCreated {
+					creator: ALICE,
+					pool_id,
+					asset_a: NativeOrAssetId::Native,
+					asset_b: asset_b.into(),
+					fee: DEFAULT_FEE_TIER,
+				},
+			));
+		});
+}
+
+#[test]
+fn swapping_native_for_asset_works() {
+	let asset_b: AssetId = 1002;
+	let amount_native: u128 = expand_to_decimals(10u128);
+	let amount_b: u128 = expand_to_decimals(50u128);
+	let swap_amount: u128 = expand_to_decimals(1u128);
+	let expected_amount_out: u128 = 45330544694;
+
+	ExtBuilder::default()
+		.with_endowed_balances(vec![(asset_b, ALICE, amount_b)])
+		.with_native_balances(vec![(ALICE, amount_native * 2)])
+		.build_and_execute(|| {
+			assert_ok!(Dex::create_pool(
+				RuntimeOrigin::signed(ALICE),
+				NativeOrAssetId::Native,
+				asset_b.into(),
+				DEFAULT_FEE_TIER
+			));
+			assert_ok!(Dex::mint(
+				RuntimeOrigin::signed(ALICE),
+				NativeOrAssetId::Native,
+				asset_b.into(),
+				amount_native,
+				amount_b,
+				0,
+				0,
+				None
+			));
+			assert_ok!(Dex::swap(
+				RuntimeOrigin::signed(ALICE),
+				NativeOrAssetId::Native,
+				asset_b.into(),
+				swap_amount,
+				None
+			));
+
+			let pool_key = AssetPair::new(NativeOrAssetId::Native, asset_b.into());
+			let pool = LiquidityPools::<Test>::get(pool_key).unwrap();
+
+			assert_eq!(pool.asset_a_balance, amount_native + swap_amount);
+			assert_eq!(pool.asset_b_balance, amount_b - expected_amount_out);
+			assert_eq!(
+				Balances::free_balance(pool.manager),
+				amount_native + swap_amount + EXISTENTIAL_DEPOSIT
+			);
+			assert_eq!(
+				Balances::free_balance(ALICE),
+				amount_native - swap_amount - POOL_DEPOSIT - EXISTENTIAL_DEPOSIT
+			);
+			assert_eq!(Fungibles::balance(asset_b, ALICE), expected_amount_out);
+
+			frame_system::Pallet::<Test>::assert_has_event(RuntimeEvent::Dex(Event::Swapped {
+				who: ALICE,
+				pool_id: pool.id,
+				asset_in: NativeOrAssetId::Native,
+				asset_out: asset_b.into(),
+				amount_in: swap_amount,
+				amount_out: expected_amount_out,
+				reserve_in: amount_native + swap_amount,
+				reserve_out: amount_b - expected_amount_out,
+			}));
+		});
+}
+
+#[test]
+fn burning_native_pool_returns_native() {
+	let asset_b: AssetId = 1002;
+	let pool_id: AssetId = 10000;
+	let amount_native: u128 = expand_to_decimals(10u128);
+	let amount_b: u128 = expand_to_decimals(50u128);
+
+	ExtBuilder::default()
+		.with_endowed_balances(vec![(asset_b, ALICE, amount_b)])
+		.with_native_balances(vec![(ALICE, amount_native * 2)])
+		.build_and_execute(|| {
+			assert_ok!(Dex::create_pool(
+				RuntimeOrigin::signed(ALICE),
+				NativeOrAssetId::Native,
+				asset_b.into(),
+				DEFAULT_FEE_TIER
+			));
+			assert_ok!(Dex::mint(
+				RuntimeOrigin::signed(ALICE),
+				NativeOrAssetId::Native,
+				asset_b.into(),
+				amount_native,
+				amount_b,
+				0,
+				0,
+				None
+			));
+			let liquidity = Fungibles::balance(pool_id, ALICE);
+
+			assert_ok!(Dex::burn(
+				RuntimeOrigin::signed(ALICE),
+				NativeOrAssetId::Native,
+				asset_b.into(),
+				liquidity,
+				0,
+				0,
+				None
+			));
+
+			let pool_key = AssetPair::new(NativeOrAssetId::Native, asset_b.into());
+			assert!(!LiquidityPools::<Test>::contains_key(pool_key));
+
+			// The last provider gets all reserves back, only the existential deposit stays in the
+			// pool account
+			assert_eq!(Balances::free_balance(Dex::pool_account(pool_id)), EXISTENTIAL_DEPOSIT);
+			assert_eq!(Balances::free_balance(ALICE), amount_native * 2 - EXISTENTIAL_DEPOSIT);
+			assert_eq!(Fungibles::balance(asset_b, ALICE), amount_b);
+			assert_eq!(Fungibles::balance(pool_id, ALICE), 0);
+		});
+}
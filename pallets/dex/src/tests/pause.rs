@@ -0,0 +1,264 @@
+use crate::tests::mock::*;
+use crate::types::AssetPair;
+use crate::{Error, Event, LiquidityPools, PausedPools};
+use frame_support::{assert_noop, assert_ok};
+use sp_runtime::DispatchError;
+
+#[test]
+fn paused_pool_rejects_swap_and_mint_but_allows_burn() {
+	let asset_a: AssetId = 1001;
+	let asset_b: AssetId = 1002;
+	let total: u128 = expand_to_decimals(100u128);
+	let amount: u128 = expand_to_decimals(10u128);
+
+	ExtBuilder::default()
+		.with_endowed_balances(vec![(asset_a, ALICE, total), (asset_b, ALICE, total)])
+		.build_and_execute(|| {
+			create_pool_with_liquidity(asset_a, asset_b, amount, amount);
+			let pool_key = AssetPair::new(asset_a.into(), asset_b.into());
+			let pool = LiquidityPools::<Test>::get(&pool_key).unwrap();
+
+			assert_ok!(Dex::pause_pool(RuntimeOrigin::root(), asset_a.into(), asset_b.into()));
+			frame_system::Pallet::<Test>::assert_has_event(RuntimeEvent::Dex(Event::PoolPaused {
+				pool_id: pool.id,
+				asset_a: asset_a.into(),
+				asset_b: asset_b.into(),
+			}));
+
+			assert_noop!(
+				Dex::swap(
+					RuntimeOrigin::signed(ALICE),
+					asset_a.into(),
+					asset_b.into(),
+					expand_to_decimals(1u128),
+					None
+				),
+				Error::<Test>::PoolPaused
+			);
+			assert_noop!(
+				Dex::flash_swap(
+					RuntimeOrigin::signed(ALICE),
+					asset_a.into(),
+					asset_b.into(),
+					expand_to_decimals(1u128),
+					0,
+					None
+				),
+				Error::<Test>::PoolPaused
+			);
+			assert_noop!(
+				Dex::mint(
+					RuntimeOrigin::signed(ALICE),
+					asset_a.into(),
+					asset_b.into(),
+					amount,
+					amount,
+					0,
+					0,
+					None
+				),
+				Error::<Test>::PoolPaused
+			);
+
+			// Liquidity providers can always exit
+			assert_ok!(Dex::burn(
+				RuntimeOrigin::signed(ALICE),
+				asset_a.into(),
+				asset_b.into(),
+				Fungibles::balance(pool.id, ALICE),
+				0,
+				0,
+				None
+			));
+			// and the pause goes with the drained pool
+			assert!(!PausedPools::<Test>::contains_key(&pool_key));
+		});
+}
+
+#[test]
+fn unpaused_pool_can_be_traded_in_again() {
+	let asset_a: AssetId = 1001;
+	let asset_b: AssetId = 1002;
+	let total: u128 = expand_to_decimals(100u128);
+	let amount: u128 = expand_to_decimals(10u128);
+
+	ExtBuilder::default()
+		.with_endowed_balances(vec![(asset_a, ALICE, total), (asset_b, ALICE, total)])
+		.build_and_execute(|| {
+			create_pool_with_liquidity(asset_a, asset_b, amount, amount);
+			let pool = LiquidityPools::<Test>::get(AssetPair::new(asset_a.into(), asset_b.into()))
+				.unwrap();
+			assert_ok!(Dex::pause_pool(RuntimeOrigin::root(), asset_a.into(), asset_b.into()));
+
+			assert_ok!(Dex::unpause_pool(RuntimeOrigin::root(), asset_a.into(), asset_b.into()));
+
+			frame_system::Pallet::<Test>::assert_has_event(RuntimeEvent::Dex(
+				Event::PoolUnpaused {
+					pool_id: pool.id,
+					asset_a: asset_a.into(),
+					asset_b: asset_b.into(),
+				},
+			));
+			assert_ok!(Dex::swap(
+				RuntimeOrigin::signed(ALICE),
+				asset_a.into(),
+				asset_b.into(),
+				expand_to_decimals(1u128),
+				None
+			));
+		});
+}
+
+#[test]
+fn disabled_trading_stops_every_pool() {
+	let asset_a: AssetId = 1001;
+	let asset_b: AssetId = 1002;
+	let asset_c: AssetId = 1003;
+	let total: u128 = expand_to_decimals(100u128);
+	let amount: u128 = expand_to_decimals(10u128);
+
+	ExtBuilder::default()
+		.with_endowed_balances(vec![
+			(asset_a, ALICE, total),
+			(asset_b, ALICE, total),
+			(asset_c, ALICE, total),
+		])
+		.build_and_execute(|| {
+			create_pool_with_liquidity(asset_a, asset_b, amount, amount);
+			create_pool_with_liquidity(asset_b, asset_c, amount, amount);
+
+			assert_ok!(Dex::set_trading_enabled(RuntimeOrigin::root(), false));
+			frame_system::Pallet::<Test>::assert_has_event(RuntimeEvent::Dex(
+				Event::TradingEnabledSet { enabled: false },
+			));
+
+			for (asset_in, asset_out) in [(asset_a, asset_b), (asset_c, asset_b)] {
+				assert_noop!(
+					Dex::swap(
+						RuntimeOrigin::signed(ALICE),
+						asset_in.into(),
+						asset_out.into(),
+						expand_to_decimals(1u128),
+						None
+					),
+					Error::<Test>::TradingDisabled
+				);
+			}
+			assert_noop!(
+				Dex::mint(
+					RuntimeOrigin::signed(ALICE),
+					asset_b.into(),
+					asset_c.into(),
+					amount,
+					amount,
+					0,
+					0,
+					None
+				),
+				Error::<Test>::TradingDisabled
+			);
+			assert_ok!(Dex::burn(
+				RuntimeOrigin::signed(ALICE),
+				asset_b.into(),
+				asset_c.into(),
+				expand_to_decimals(1u128),
+				0,
+				0,
+				None
+			));
+
+			assert_ok!(Dex::set_trading_enabled(RuntimeOrigin::root(), true));
+			assert_ok!(Dex::swap(
+				RuntimeOrigin::signed(ALICE),
+				asset_a.into(),
+				asset_b.into(),
+				expand_to_decimals(1u128),
+				None
+			));
+		});
+}
+
+#[test]
+fn pausing_fails_for_wrong_origin_or_state() {
+	let asset_a: AssetId = 1001;
+	let asset_b: AssetId = 1002;
+	let asset_c: AssetId = 1003;
+	let total: u128 = expand_to_decimals(100u128);
+
+	ExtBuilder::default()
+		.with_endowed_balances(vec![
+			(asset_a, ALICE, total),
+			(asset_b, ALICE, total),
+			(asset_c, ALICE, total),
+		])
+		.build_and_execute(|| {
+			create_pool_with_liquidity(
+				asset_a,
+				asset_b,
+				expand_to_decimals(10u128),
+				expand_to_decimals(10u128),
+			);
+
+			assert_noop!(
+				Dex::pause_pool(RuntimeOrigin::signed(ALICE), asset_a.into(), asset_b.into()),
+				DispatchError::BadOrigin
+			);
+			assert_noop!(
+				Dex::set_trading_enabled(RuntimeOrigin::signed(ALICE), false),
+				DispatchError::BadOrigin
+			);
+			assert_noop!(
+				Dex::pause_pool(RuntimeOrigin::root(), asset_a.into(), asset_c.into()),
+				Error::<Test>::LiquidityPoolDoesNotExist
+			);
+			assert_noop!(
+				Dex::unpause_pool(RuntimeOrigin::root(), asset_a.into(), asset_b.into()),
+				Error::<Test>::PoolNotPaused
+			);
+
+			assert_ok!(Dex::pause_pool(RuntimeOrigin::root(), asset_a.into(), asset_b.into()));
+			assert_noop!(
+				Dex::pause_pool(RuntimeOrigin::root(), asset_a.into(), asset_b.into()),
+				Error::<Test>::PoolPaused
+			);
+			assert_noop!(
+				Dex::unpause_pool(RuntimeOrigin::signed(ALICE), asset_a.into(), asset_b.into()),
+				DispatchError::BadOrigin
+			);
+		});
+}
+
+#[test]
+fn best_route_avoids_paused_pools_and_disabled_trading() {
+	let asset_a: AssetId = 1001;
+	let asset_b: AssetId = 1002;
+	let asset_c: AssetId = 1003;
+	let total: u128 = expand_to_decimals(100u128);
+	let amount: u128 = expand_to_decimals(10u128);
+
+	ExtBuilder::default()
+		.with_endowed_balances(vec![
+			(asset_a, ALICE, total),
+			(asset_b, ALICE, total),
+			(asset_c, ALICE, total),
+		])
+		.build_and_execute(|| {
+			create_pool_with_liquidity(asset_a, asset_b, amount, amount);
+			create_pool_with_liquidity(asset_b, asset_c, amount, amount);
+			let amount_in = expand_to_decimals(1u128);
+			assert_ok!(Dex::find_best_route(asset_a.into(), amount_in, asset_c.into(), 2));
+
+			assert_ok!(Dex::pause_pool(RuntimeOrigin::root(), asset_b.into(), asset_c.into()));
+			assert_noop!(
+				Dex::find_best_route(asset_a.into(), amount_in, asset_c.into(), 2),
+				Error::<Test>::NoRouteFound
+			);
+
+			assert_ok!(Dex::unpause_pool(RuntimeOrigin::root(), asset_b.into(), asset_c.into()));
+			assert_ok!(Dex::set_trading_enabled(RuntimeOrigin::root(), false));
+			assert_noop!(
+				Dex::find_best_route(asset_a.into(), amount_in, asset_c.into(), 2),
+				Error::<Test>::TradingDisabled
+			);
+		});
+}
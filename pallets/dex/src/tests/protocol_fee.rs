@@ -0,0 +1,157 @@
+use crate::tests::mock::*;
+use crate::types::AssetPair;
+use crate::{Event, LiquidityPools, ProtocolFee};
+use frame_support::{assert_noop, assert_ok};
+use sp_runtime::{DispatchError, Perbill};
+
+#[test]
+fn setting_protocol_fee_works() {
+	ExtBuilder::default().build_and_execute(|| {
+		let protocol_fee = Some((BOB, Perbill::from_percent(20)));
+		assert_ok!(Dex::set_protocol_fee(RuntimeOrigin::root(), protocol_fee.clone()));
+		assert_eq!(ProtocolFee::<Test>::get(), protocol_fee);
+
+		assert_ok!(Dex::set_protocol_fee(RuntimeOrigin::root(), None));
+		assert_eq!(ProtocolFee::<Test>::get(), None);
+
+		// Ensure correct events are triggered
+		frame_system::Pallet::<Test>::assert_has_event(RuntimeEvent::Dex(Event::ProtocolFeeSet {
+			protocol_fee,
+		}));
+		frame_system::Pallet::<Test>::assert_has_event(RuntimeEvent::Dex(Event::ProtocolFeeSet {
+			protocol_fee: None,
+		}));
+	});
+}
+
+#[test]
+fn setting_protocol_fee_fails_on_non_root_origin() {
+	ExtBuilder::default().build_and_execute(|| {
+		assert_noop!(
+			Dex::set_protocol_fee(
+				RuntimeOrigin::signed(ALICE),
+				Some((ALICE, Perbill::from_percent(20)))
+			),
+			DispatchError::BadOrigin
+		);
+	});
+}
+
+#[test]
+fn protocol_fee_is_minted_on_burn() {
+	let asset_a: AssetId = 1001;
+	let asset_b: AssetId = 1002;
+	let total: u128 = expand_to_decimals(100u128);
+	let amount: u128 = expand_to_decimals(10u128);
+
+	ExtBuilder::default()
+		.with_endowed_balances(vec![(asset_a, ALICE, total), (asset_b, ALICE, total)])
+		.build_and_execute(|| {
+			assert_ok!(Dex::set_protocol_fee(
+				RuntimeOrigin::root(),
+				Some((BOB, Perbill::from_percent(20)))
+			));
+			assert_ok!(Dex::create_pool(
+				RuntimeOrigin::signed(ALICE),
+				asset_a.into(),
+				asset_b.into(),
+				DEFAULT_FEE_TIER
+			));
+			assert_ok!(Dex::mint(
+				RuntimeOrigin::signed(ALICE),
+				asset_a.into(),
+				asset_b.into(),
+				amount,
+				amount,
+				0,
+				0,
+				None
+			));
+
+			let pool_key = AssetPair::new(asset_a.into(), asset_b.into());
+			let pool = LiquidityPools::<Test>::get(pool_key.clone()).unwrap();
+			assert_eq!(pool.k_last, amount * amount);
+			// No fees have been collected yet
+			assert_eq!(Fungibles::balance(pool.id, BOB), 0);
+
+			assert_ok!(Dex::swap(
+				RuntimeOrigin::signed(ALICE),
+				asset_a.into(),
+				asset_b.into(),
+				expand_to_decimals(1u128),
+				None
+			));
+			assert_ok!(Dex::burn(
+				RuntimeOrigin::signed(ALICE),
+				asset_a.into(),
+				asset_b.into(),
+				expand_to_decimals(1u128),
+				0,
+				0,
+				None
+			));
+
+			// The protocol receives its share of the fee growth from the swap
+			let pool = LiquidityPools::<Test>::get(pool_key).unwrap();
+			assert_eq!(Fungibles::balance(pool.id, BOB), 2727532);
+			assert_eq!(
+				Fungibles::total_supply(pool.id),
+				amount + 2727532 - expand_to_decimals(1u128)
+			);
+
+			// The burn is paid out against the supply including the protocol fee
+			assert_eq!(pool.asset_a_balance, 110000000000 - 10999699979);
+			assert_eq!(pool.asset_b_balance, 90933891062 - 9093141087);
+			assert_eq!(pool.k_last, pool.asset_a_balance * pool.asset_b_balance);
+		});
+}
+
+#[test]
+fn protocol_fee_is_not_minted_when_off() {
+	let asset_a: AssetId = 1001;
+	let asset_b: AssetId = 1002;
+	let total: u128 = expand_to_decimals(100u128);
+	let amount: u128 = expand_to_decimals(10u128);
+
+	ExtBuilder::default()
+		.with_endowed_balances(vec![(asset_a, ALICE, total), (asset_b, ALICE, total)])
+		.build_and_execute(|| {
+			assert_ok!(Dex::create_pool(
+				RuntimeOrigin::signed(ALICE),
+				asset_a.into(),
+				asset_b.into(),
+				DEFAULT_FEE_TIER
+			));
+			assert_ok!(Dex::mint(
+				RuntimeOrigin::signed(ALICE),
+				asset_a.into(),
+				asset_b.into(),
+				amount,
+				amount,
+				0,
+				0,
+				None
+			));
+			assert_ok!(Dex::swap(
+				RuntimeOrigin::signed(ALICE),
+				asset_a.into(),
+				asset_b.into(),
+				expand_to_decimals(1u128),
+				None
+			));
+			assert_ok!(Dex::burn(
+				RuntimeOrigin::signed(ALICE),
+				asset_a.into(),
+				asset_b.into(),
+				expand_to_decimals(1u128),
+				0,
+				0,
+				None
+			));
+
+			let pool = LiquidityPools::<Test>::get(AssetPair::new(asset_a.into(), asset_b.into()))
+				.unwrap();
+			assert_eq!(pool.k_last, 0);
+			assert_eq!(Fungibles::total_supply(pool.id), amount - expand_to_decimals(1u128));
+		});
+}
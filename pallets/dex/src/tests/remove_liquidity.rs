@@ -1,6 +1,7 @@
 use crate::tests::mock::*;
 use crate::types::AssetPair;
-use crate::{Error, Event, LiquidityPools};
+use crate::{Error, Event, HoldReason, LiquidityPools, PriceObservations};
+use frame_support::traits::fungible::InspectHold;
 use frame_support::{assert_noop, assert_ok};
 
 #[test]
@@ -13,42 +14,62 @@ fn burn_works() {
 
 	ExtBuilder::default()
 		.with_endowed_balances(vec![(asset_a, ALICE, amount_a), (asset_b, ALICE, amount_b)])
-		.build()
-		.execute_with(|| {
+		.build_and_execute(|| {
 			let expected_liquidity = expand_to_decimals(3u128) - MIN_LIQUIDITY;
 
+			assert_ok!(Dex::create_pool(
+				RuntimeOrigin::signed(ALICE),
+				asset_a.into(),
+				asset_b.into(),
+				DEFAULT_FEE_TIER
+			));
 			assert_ok!(Dex::mint(
 				RuntimeOrigin::signed(ALICE),
-				pool_id,
-				asset_a,
-				asset_b,
+				asset_a.into(),
+				asset_b.into(),
 				amount_a,
 				amount_b,
+				0,
+				0,
+				None
 			));
 
 			assert_ok!(Dex::burn(
 				RuntimeOrigin::signed(ALICE),
-				asset_a,
-				asset_b,
-				expected_liquidity
+				asset_a.into(),
+				asset_b.into(),
+				expected_liquidity,
+				0,
+				0,
+				None
 			));
 
-			let pool_key = AssetPair::new(asset_a, asset_b);
+			let pool_key = AssetPair::new(asset_a.into(), asset_b.into());
 			let pool = LiquidityPools::<Test>::get(pool_key);
 
 			// Pool should be removed when all liquidity is drained
 			assert_eq!(pool, None);
+			// and the creation deposit released, while the existential deposit stays with the pool
+			// account
+			assert_eq!(Balances::free_balance(ALICE), POOL_DEPOSIT * 10 - EXISTENTIAL_DEPOSIT);
 
-			let token_a_issuance = Fungibles::total_supply(asset_a);
-			let token_b_issuance = Fungibles::total_supply(asset_b);
-
-			// User balances have been updated
-			assert_eq!(Fungibles::balance(asset_a, ALICE), token_a_issuance - MIN_LIQUIDITY);
-			assert_eq!(Fungibles::balance(asset_b, ALICE), token_b_issuance - MIN_LIQUIDITY);
+			// The last provider also gets the reserves backing the locked minimum liquidity
+			assert_eq!(Fungibles::balance(asset_a, ALICE), amount_a);
+			assert_eq!(Fungibles::balance(asset_b, ALICE), amount_b);
 
 			// Ensure correct events are triggered
 			frame_system::Pallet::<Test>::assert_has_event(RuntimeEvent::Dex(
-				Event::LiquidityRemoved(asset_a, asset_b, expected_liquidity),
+				Event::LiquidityRemoved {
+					who: ALICE,
+					pool_id,
+					asset_a: asset_a.into(),
+					asset_b: asset_b.into(),
+					amount_a,
+					amount_b,
+					lp_burned: expected_liquidity,
+					reserve_a: 0,
+					reserve_b: 0,
+				},
 			));
 		});
 }
@@ -57,47 +78,128 @@ fn burn_works() {
 fn burn_works_when_burning_max_lp() {
 	let asset_a: AssetId = 1001;
 	let asset_b: AssetId = 1002;
-	let pool_id: AssetId = 10000;
 	let amount_a: u128 = expand_to_decimals(3u128);
 	let amount_b: u128 = expand_to_decimals(3u128);
 
 	ExtBuilder::default()
 		.with_endowed_balances(vec![(asset_a, ALICE, amount_a), (asset_b, ALICE, amount_b)])
-		.build()
-		.execute_with(|| {
+		.build_and_execute(|| {
+			assert_ok!(Dex::create_pool(
+				RuntimeOrigin::signed(ALICE),
+				asset_a.into(),
+				asset_b.into(),
+				DEFAULT_FEE_TIER
+			));
 			assert_ok!(Dex::mint(
 				RuntimeOrigin::signed(ALICE),
-				pool_id,
-				asset_a,
-				asset_b,
+				asset_a.into(),
+				asset_b.into(),
 				amount_a,
 				amount_b,
+				0,
+				0,
+				None
 			));
 
-			let pool_key = AssetPair::new(asset_a, asset_b);
+			let pool_key = AssetPair::new(asset_a.into(), asset_b.into());
 			let pool = LiquidityPools::<Test>::get(pool_key).unwrap();
 			let max_burn_amount = Fungibles::balance(pool.id, ALICE);
-			assert_ok!(Dex::burn(RuntimeOrigin::signed(ALICE), asset_a, asset_b, max_burn_amount));
+			assert_ok!(Dex::burn(
+				RuntimeOrigin::signed(ALICE),
+				asset_a.into(),
+				asset_b.into(),
+				max_burn_amount,
+				0,
+				0,
+				None
+			));
 
 			// Burning of LP tokens successful
 			assert_eq!(Fungibles::balance(pool.id, ALICE), 0);
 			assert_eq!(Fungibles::total_supply(pool.id), MIN_LIQUIDITY);
 
-			// Pallet manager balances have been updated
-			assert_eq!(Fungibles::balance(asset_a, pool.manager), MIN_LIQUIDITY);
-			assert_eq!(Fungibles::balance(asset_b, pool.manager), MIN_LIQUIDITY);
-
-			let token_a_issuance = Fungibles::total_supply(asset_a);
-			let token_b_issuance = Fungibles::total_supply(asset_b);
+			// The pool account is emptied
+			assert_eq!(Fungibles::balance(asset_a, pool.manager), 0);
+			assert_eq!(Fungibles::balance(asset_b, pool.manager), 0);
 
 			// User balances have been updated
-			assert_eq!(Fungibles::balance(asset_a, ALICE), token_a_issuance - MIN_LIQUIDITY);
-			assert_eq!(Fungibles::balance(asset_b, ALICE), token_b_issuance - MIN_LIQUIDITY);
+			assert_eq!(Fungibles::balance(asset_a, ALICE), amount_a);
+			assert_eq!(Fungibles::balance(asset_b, ALICE), amount_b);
 
 			// Ensure correct events are triggered
 			frame_system::Pallet::<Test>::assert_has_event(RuntimeEvent::Dex(
-				Event::LiquidityRemoved(asset_a, asset_b, max_burn_amount),
+				Event::LiquidityRemoved {
+					who: ALICE,
+					pool_id: pool.id,
+					asset_a: asset_a.into(),
+					asset_b: asset_b.into(),
+					amount_a,
+					amount_b,
+					lp_burned: max_burn_amount,
+					reserve_a: 0,
+					reserve_b: 0,
+				},
+			));
+		});
+}
+
+#[test]
+fn burn_destroys_pool_with_unequal_reserves() {
+	let asset_a: AssetId = 1001;
+	let asset_b: AssetId = 1002;
+	let total: u128 = expand_to_decimals(100u128);
+	let amount_a: u128 = expand_to_decimals(10u128);
+	let amount_b: u128 = expand_to_decimals(40u128);
+
+	ExtBuilder::default()
+		.with_endowed_balances(vec![(asset_a, ALICE, total), (asset_b, ALICE, total)])
+		.build_and_execute(|| {
+			assert_ok!(Dex::create_pool(
+				RuntimeOrigin::signed(ALICE),
+				asset_a.into(),
+				asset_b.into(),
+				DEFAULT_FEE_TIER
+			));
+			assert_ok!(Dex::mint(
+				RuntimeOrigin::signed(ALICE),
+				asset_a.into(),
+				asset_b.into(),
+				amount_a,
+				amount_b,
+				0,
+				0,
+				None
 			));
+			assert_ok!(Dex::swap(
+				RuntimeOrigin::signed(ALICE),
+				asset_a.into(),
+				asset_b.into(),
+				expand_to_decimals(1u128),
+				None
+			));
+			let pool_key = AssetPair::new(asset_a.into(), asset_b.into());
+			let pool = LiquidityPools::<Test>::get(&pool_key).unwrap();
+
+			assert_ok!(Dex::burn(
+				RuntimeOrigin::signed(ALICE),
+				asset_a.into(),
+				asset_b.into(),
+				Fungibles::balance(pool.id, ALICE),
+				0,
+				0,
+				None
+			));
+
+			// The pool is destroyed once only the locked minimum liquidity is left
+			assert!(!LiquidityPools::<Test>::contains_key(&pool_key));
+			assert!(!PriceObservations::<Test>::contains_key(&pool_key));
+			assert_eq!(Balances::balance_on_hold(&HoldReason::PoolDeposit.into(), &ALICE), 0);
+			assert_eq!(Balances::free_balance(ALICE), POOL_DEPOSIT * 10 - EXISTENTIAL_DEPOSIT);
+			// and the last provider gets all of its reserves
+			assert_eq!(Fungibles::balance(asset_a, pool.manager), 0);
+			assert_eq!(Fungibles::balance(asset_b, pool.manager), 0);
+			assert_eq!(Fungibles::balance(asset_a, ALICE), total);
+			assert_eq!(Fungibles::balance(asset_b, ALICE), total);
 		});
 }
 
@@ -105,8 +207,6 @@ fn burn_works_when_burning_max_lp() {
 fn burn_amounts_works_correctly() {
 	let asset_a: AssetId = 1001;
 	let asset_b: AssetId = 1002;
-	let pool_id: AssetId = 10000;
-	let pool_id_2: AssetId = 10001;
 	let total_a: u128 = expand_to_decimals(100u128);
 	let total_b: u128 = expand_to_decimals(100u128);
 	let amount_a: u128 = expand_to_decimals(10u128);
@@ -116,36 +216,53 @@ fn burn_amounts_works_correctly() {
 
 	ExtBuilder::default()
 		.with_endowed_balances(vec![(asset_a, ALICE, total_a), (asset_b, ALICE, total_b)])
-		.build()
-		.execute_with(|| {
+		.build_and_execute(|| {
 			let expected_liquidity = expand_to_decimals(25u128);
+			assert_ok!(Dex::create_pool(
+				RuntimeOrigin::signed(ALICE.into()),
+				asset_a.into(),
+				asset_b.into(),
+				DEFAULT_FEE_TIER
+			));
 			assert_ok!(Dex::mint(
 				RuntimeOrigin::signed(ALICE.into()),
-				pool_id,
-				asset_a,
-				asset_b,
+				asset_a.into(),
+				asset_b.into(),
 				amount_a,
-				amount_b
+				amount_b,
+				0,
+				0,
+				None
 			));
-			let pool_key = AssetPair { asset_a, asset_b };
+			let pool_key = AssetPair { asset_a: asset_a.into(), asset_b: asset_b.into() };
 			let mut pool = LiquidityPools::<Test>::get(pool_key.clone()).unwrap();
 			assert_eq!(Fungibles::total_supply(pool.id), expand_to_decimals(20u128));
 
 			assert_ok!(Dex::mint(
 				RuntimeOrigin::signed(ALICE.into()),
-				pool_id_2,
-				asset_a,
-				asset_b,
+				asset_a.into(),
+				asset_b.into(),
 				second_amount_a,
-				second_amount_b
+				second_amount_b,
+				0,
+				0,
+				None
 			));
 
 			let burn_amount = expand_to_decimals(1u128);
 
 			// Internal pool balances should be updated
-			assert_ok!(Dex::burn(RuntimeOrigin::signed(ALICE), asset_a, asset_b, burn_amount));
+			assert_ok!(Dex::burn(
+				RuntimeOrigin::signed(ALICE),
+				asset_a.into(),
+				asset_b.into(),
+				burn_amount,
+				0,
+				0,
+				None
+			));
 			pool = LiquidityPools::<Test>::get(pool_key).unwrap();
-			assert_eq!(pool.asset_a_balance, 576000000000);
+			assert_eq!(pool.asset_a_balance, 120000000000);
 			assert_eq!(pool.asset_b_balance, 480000000000);
 
 			// Burning of LP tokens successful
@@ -156,16 +273,26 @@ fn burn_amounts_works_correctly() {
 			assert_eq!(Fungibles::total_supply(pool.id), expected_liquidity - burn_amount);
 
 			// Pallet manager balances have been updated
-			assert_eq!(Fungibles::balance(asset_a, pool.manager), 576000000000);
+			assert_eq!(Fungibles::balance(asset_a, pool.manager), 120000000000);
 			assert_eq!(Fungibles::balance(asset_b, pool.manager), 480000000000);
 
 			// User balances have been updated
-			assert_eq!(Fungibles::balance(asset_a, ALICE), 424000000000);
+			assert_eq!(Fungibles::balance(asset_a, ALICE), 880000000000);
 			assert_eq!(Fungibles::balance(asset_b, ALICE), 520000000000);
 
 			// Ensure correct events are triggered
 			frame_system::Pallet::<Test>::assert_has_event(RuntimeEvent::Dex(
-				Event::LiquidityRemoved(asset_a, asset_b, burn_amount),
+				Event::LiquidityRemoved {
+					who: ALICE,
+					pool_id: pool.id,
+					asset_a: asset_a.into(),
+					asset_b: asset_b.into(),
+					amount_a: 5000000000,
+					amount_b: 20000000000,
+					lp_burned: burn_amount,
+					reserve_a: 120000000000,
+					reserve_b: 480000000000,
+				},
 			));
 		});
 }
@@ -174,25 +301,39 @@ fn burn_amounts_works_correctly() {
 fn burn_fails_on_identical_assets() {
 	let asset_a: AssetId = 1001;
 	let asset_b: AssetId = 1002;
-	let pool_id: AssetId = 10000;
 	let amount_a: u128 = expand_to_decimals(1u128);
 	let amount_b: u128 = expand_to_decimals(4u128);
 
 	ExtBuilder::default()
 		.with_endowed_balances(vec![(asset_a, ALICE, amount_a), (asset_b, ALICE, amount_b)])
-		.build()
-		.execute_with(|| {
+		.build_and_execute(|| {
+			assert_ok!(Dex::create_pool(
+				RuntimeOrigin::signed(ALICE.into()),
+				asset_a.into(),
+				asset_b.into(),
+				DEFAULT_FEE_TIER
+			));
 			assert_ok!(Dex::mint(
 				RuntimeOrigin::signed(ALICE.into()),
-				pool_id,
-				asset_a,
-				asset_b,
+				asset_a.into(),
+				asset_b.into(),
 				amount_a,
-				amount_b
+				amount_b,
+				0,
+				0,
+				None
 			));
 			let burn_amount = expand_to_decimals(1u128);
 			assert_noop!(
-				Dex::burn(RuntimeOrigin::signed(ALICE), asset_a, asset_a, burn_amount),
+				Dex::burn(
+					RuntimeOrigin::signed(ALICE),
+					asset_a.into(),
+					asset_a.into(),
+					burn_amount,
+					0,
+					0,
+					None
+				),
 				Error::<Test>::IdenticalAssets
 			);
 		});
@@ -207,11 +348,18 @@ fn burn_fails_on_nonexistent_pool() {
 
 	ExtBuilder::default()
 		.with_endowed_balances(vec![(asset_a, ALICE, amount_a), (asset_b, ALICE, amount_b)])
-		.build()
-		.execute_with(|| {
+		.build_and_execute(|| {
 			let burn_amount = expand_to_decimals(1u128);
 			assert_noop!(
-				Dex::burn(RuntimeOrigin::signed(ALICE), asset_a, asset_b, burn_amount),
+				Dex::burn(
+					RuntimeOrigin::signed(ALICE),
+					asset_a.into(),
+					asset_b.into(),
+					burn_amount,
+					0,
+					0,
+					None
+				),
 				Error::<Test>::LiquidityPoolDoesNotExist
 			);
 		});
@@ -221,26 +369,148 @@ fn burn_fails_on_nonexistent_pool() {
 fn burn_fails_on_insufficient_lp_balance() {
 	let asset_a: AssetId = 1001;
 	let asset_b: AssetId = 1002;
-	let pool_id: AssetId = 10000;
 	let amount_a: u128 = expand_to_decimals(1u128);
 	let amount_b: u128 = expand_to_decimals(4u128);
 
 	ExtBuilder::default()
 		.with_endowed_balances(vec![(asset_a, ALICE, amount_a), (asset_b, ALICE, amount_b)])
-		.build()
-		.execute_with(|| {
+		.build_and_execute(|| {
+			assert_ok!(Dex::create_pool(
+				RuntimeOrigin::signed(ALICE.into()),
+				asset_a.into(),
+				asset_b.into(),
+				DEFAULT_FEE_TIER
+			));
 			assert_ok!(Dex::mint(
 				RuntimeOrigin::signed(ALICE.into()),
-				pool_id,
-				asset_a,
-				asset_b,
+				asset_a.into(),
+				asset_b.into(),
 				amount_a,
-				amount_b
+				amount_b,
+				0,
+				0,
+				None
 			));
 			let burn_amount = expand_to_decimals(3u128);
 			assert_noop!(
-				Dex::burn(RuntimeOrigin::signed(ALICE), asset_a, asset_b, burn_amount),
+				Dex::burn(
+					RuntimeOrigin::signed(ALICE),
+					asset_a.into(),
+					asset_b.into(),
+					burn_amount,
+					0,
+					0,
+					None
+				),
 				Error::<Test>::InsufficientBurnBalance
 			);
 		});
 }
+
+#[test]
+fn burn_fails_after_deadline() {
+	let asset_a: AssetId = 1001;
+	let asset_b: AssetId = 1002;
+	let amount_a: u128 = expand_to_decimals(3u128);
+	let amount_b: u128 = expand_to_decimals(3u128);
+
+	ExtBuilder::default()
+		.with_endowed_balances(vec![(asset_a, ALICE, amount_a), (asset_b, ALICE, amount_b)])
+		.build_and_execute(|| {
+			assert_ok!(Dex::create_pool(
+				RuntimeOrigin::signed(ALICE),
+				asset_a.into(),
+				asset_b.into(),
+				DEFAULT_FEE_TIER
+			));
+			assert_ok!(Dex::mint(
+				RuntimeOrigin::signed(ALICE),
+				asset_a.into(),
+				asset_b.into(),
+				amount_a,
+				amount_b,
+				0,
+				0,
+				None
+			));
+
+			System::set_block_number(5);
+			assert_noop!(
+				Dex::burn(
+					RuntimeOrigin::signed(ALICE),
+					asset_a.into(),
+					asset_b.into(),
+					expand_to_decimals(1u128),
+					0,
+					0,
+					Some(4)
+				),
+				Error::<Test>::Expired
+			);
+		});
+}
+
+#[test]
+fn burn_fails_below_amount_min() {
+	let asset_a: AssetId = 1001;
+	let asset_b: AssetId = 1002;
+	let amount_a: u128 = expand_to_decimals(10u128);
+	let amount_b: u128 = expand_to_decimals(40u128);
+	let burn_amount: u128 = expand_to_decimals(1u128);
+
+	ExtBuilder::default()
+		.with_endowed_balances(vec![(asset_a, ALICE, amount_a), (asset_b, ALICE, amount_b)])
+		.build_and_execute(|| {
+			assert_ok!(Dex::create_pool(
+				RuntimeOrigin::signed(ALICE),
+				asset_a.into(),
+				asset_b.into(),
+				DEFAULT_FEE_TIER
+			));
+			assert_ok!(Dex::mint(
+				RuntimeOrigin::signed(ALICE),
+				asset_a.into(),
+				asset_b.into(),
+				amount_a,
+				amount_b,
+				0,
+				0,
+				None
+			));
+
+			// Burning 1 of the 20 LP tokens returns 0.5 of asset_a and 2 of asset_b
+			assert_noop!(
+				Dex::burn(
+					RuntimeOrigin::signed(ALICE),
+					asset_a.into(),
+					asset_b.into(),
+					burn_amount,
+					expand_to_decimals(1u128),
+					0,
+					None
+				),
+				Error::<Test>::InsufficientAmountA
+			);
+			assert_noop!(
+				Dex::burn(
+					RuntimeOrigin::signed(ALICE),
+					asset_a.into(),
+					asset_b.into(),
+					burn_amount,
+					0,
+					expand_to_decimals(3u128),
+					None
+				),
+				Error::<Test>::InsufficientAmountB
+			);
+			assert_ok!(Dex::burn(
+				RuntimeOrigin::signed(ALICE),
+				asset_a.into(),
+				asset_b.into(),
+				burn_amount,
+				expand_to_decimals(1u128) / 2,
+				expand_to_decimals(2u128),
+				None
+			));
+		});
+}
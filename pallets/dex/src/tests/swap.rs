@@ -1,13 +1,14 @@
 use crate::tests::mock::*;
+use crate::traits::OraclePrice;
 use crate::types::AssetPair;
-use crate::{Error, LiquidityPools};
-use frame_support::{assert_noop, assert_ok};
+use crate::{Error, Event, LiquidityPools};
+use frame_support::{assert_noop, assert_ok, BoundedVec};
+use sp_runtime::Permill;
 
 #[test]
 fn swapping_token_a_works() {
 	let asset_a: AssetId = 1001;
 	let asset_b: AssetId = 1002;
-	let pool_id: AssetId = 10000;
 	let total_a: u128 = expand_to_decimals(100u128);
 	let total_b: u128 = expand_to_decimals(100u128);
 	let amount_a: u128 = expand_to_decimals(10u128);
@@ -15,20 +16,33 @@ fn swapping_token_a_works() {
 
 	ExtBuilder::default()
 		.with_endowed_balances(vec![(asset_a, ALICE, total_a), (asset_b, ALICE, total_b)])
-		.build()
-		.execute_with(|| {
+		.build_and_execute(|| {
 			let swap_amount = expand_to_decimals(1u128);
+			assert_ok!(Dex::create_pool(
+				RuntimeOrigin::signed(ALICE),
+				asset_a.into(),
+				asset_b.into(),
+				DEFAULT_FEE_TIER
+			));
 			assert_ok!(Dex::mint(
 				RuntimeOrigin::signed(ALICE),
-				pool_id,
-				asset_a,
-				asset_b,
+				asset_a.into(),
+				asset_b.into(),
 				amount_a,
-				amount_b
+				amount_b,
+				0,
+				0,
+				None
+			));
+			assert_ok!(Dex::swap(
+				RuntimeOrigin::signed(ALICE),
+				asset_a.into(),
+				asset_b.into(),
+				swap_amount,
+				None
 			));
-			assert_ok!(Dex::swap(RuntimeOrigin::signed(ALICE), asset_a, asset_b, swap_amount));
 
-			let pool_key = AssetPair::new(asset_a, asset_b);
+			let pool_key = AssetPair::new(asset_a.into(), asset_b.into());
 			let pool = LiquidityPools::<Test>::get(pool_key).unwrap();
 
 			assert_eq!(Fungibles::balance(asset_a, pool.manager), expand_to_decimals(11u128));
@@ -46,28 +60,40 @@ fn swapping_token_a_works() {
 fn swapping_token_b_works() {
 	let asset_a: AssetId = 1001;
 	let asset_b: AssetId = 1002;
-	let pool_id: AssetId = 10000;
 	let amount_a: u128 = expand_to_decimals(50u128);
 	let amount_b: u128 = expand_to_decimals(10u128);
 	ExtBuilder::default()
 		.with_endowed_balances(vec![(asset_a, ALICE, amount_a), (asset_b, ALICE, amount_b)])
-		.build()
-		.execute_with(|| {
+		.build_and_execute(|| {
 			let mint_amount_b = expand_to_decimals(5u128);
 			let swap_amount = expand_to_decimals(1u128);
 
+			assert_ok!(Dex::create_pool(
+				RuntimeOrigin::signed(ALICE),
+				asset_a.into(),
+				asset_b.into(),
+				DEFAULT_FEE_TIER
+			));
 			assert_ok!(Dex::mint(
 				RuntimeOrigin::signed(ALICE),
-				pool_id,
-				asset_a,
-				asset_b,
+				asset_a.into(),
+				asset_b.into(),
 				amount_b,
-				mint_amount_b
+				mint_amount_b,
+				0,
+				0,
+				None
 			));
 
-			assert_ok!(Dex::swap(RuntimeOrigin::signed(ALICE), asset_b, asset_a, swap_amount));
+			assert_ok!(Dex::swap(
+				RuntimeOrigin::signed(ALICE),
+				asset_b.into(),
+				asset_a.into(),
+				swap_amount,
+				None
+			));
 
-			let pool_key = AssetPair::new(asset_a, asset_b);
+			let pool_key = AssetPair::new(asset_a.into(), asset_b.into());
 			let pool = LiquidityPools::<Test>::get(pool_key).unwrap();
 
 			let pool_asset_a = decimals_to_numeric(Fungibles::balance(asset_a, pool.manager));
@@ -90,11 +116,16 @@ fn swapping_fails_on_non_existing_pool() {
 	let amount_b: u128 = expand_to_decimals(10u128);
 	ExtBuilder::default()
 		.with_endowed_balances(vec![(asset_a, ALICE, amount_a), (asset_b, ALICE, amount_b)])
-		.build()
-		.execute_with(|| {
+		.build_and_execute(|| {
 			let swap_amount = expand_to_decimals(1u128);
 			assert_noop!(
-				Dex::swap(RuntimeOrigin::signed(ALICE), asset_a, asset_b, swap_amount),
+				Dex::swap(
+					RuntimeOrigin::signed(ALICE),
+					asset_a.into(),
+					asset_b.into(),
+					swap_amount,
+					None
+				),
 				Error::<Test>::LiquidityPoolDoesNotExist
 			);
 		});
@@ -108,11 +139,16 @@ fn swapping_fails_on_idential_assets() {
 	let amount_b: u128 = expand_to_decimals(10u128);
 	ExtBuilder::default()
 		.with_endowed_balances(vec![(asset_a, ALICE, amount_a), (asset_b, ALICE, amount_b)])
-		.build()
-		.execute_with(|| {
+		.build_and_execute(|| {
 			let swap_amount = expand_to_decimals(1u128);
 			assert_noop!(
-				Dex::swap(RuntimeOrigin::signed(ALICE), asset_a, asset_a, swap_amount),
+				Dex::swap(
+					RuntimeOrigin::signed(ALICE),
+					asset_a.into(),
+					asset_a.into(),
+					swap_amount,
+					None
+				),
 				Error::<Test>::IdenticalAssets
 			);
 		});
@@ -122,23 +158,29 @@ fn swapping_fails_on_idential_assets() {
 fn swapping_fails_on_zero_amount_in() {
 	let asset_a: AssetId = 1001;
 	let asset_b: AssetId = 1002;
-	let pool_id: AssetId = 10000;
 	let amount_a: u128 = expand_to_decimals(50u128);
 	let amount_b: u128 = expand_to_decimals(10u128);
 	ExtBuilder::default()
 		.with_endowed_balances(vec![(asset_a, ALICE, amount_a), (asset_b, ALICE, amount_b)])
-		.build()
-		.execute_with(|| {
+		.build_and_execute(|| {
+			assert_ok!(Dex::create_pool(
+				RuntimeOrigin::signed(ALICE),
+				asset_a.into(),
+				asset_b.into(),
+				DEFAULT_FEE_TIER
+			));
 			assert_ok!(Dex::mint(
 				RuntimeOrigin::signed(ALICE),
-				pool_id,
-				asset_a,
-				asset_b,
+				asset_a.into(),
+				asset_b.into(),
 				amount_a,
-				amount_b
+				amount_b,
+				0,
+				0,
+				None
 			));
 			assert_noop!(
-				Dex::swap(RuntimeOrigin::signed(ALICE), asset_a, asset_b, 0),
+				Dex::swap(RuntimeOrigin::signed(ALICE), asset_a.into(), asset_b.into(), 0, None),
 				Error::<Test>::InsufficientInputAmount
 			);
 		});
@@ -148,25 +190,652 @@ fn swapping_fails_on_zero_amount_in() {
 fn swapping_fails_on_greater_than_pool_amount() {
 	let asset_a: AssetId = 1001;
 	let asset_b: AssetId = 1002;
-	let pool_id: AssetId = 10000;
 	let amount_a: u128 = expand_to_decimals(50u128);
 	let amount_b: u128 = expand_to_decimals(10u128);
 	ExtBuilder::default()
 		.with_endowed_balances(vec![(asset_a, ALICE, amount_a), (asset_b, ALICE, amount_b)])
-		.build()
-		.execute_with(|| {
+		.build_and_execute(|| {
+			assert_ok!(Dex::create_pool(
+				RuntimeOrigin::signed(ALICE),
+				asset_a.into(),
+				asset_b.into(),
+				DEFAULT_FEE_TIER
+			));
 			assert_ok!(Dex::mint(
 				RuntimeOrigin::signed(ALICE),
-				pool_id,
-				asset_a,
-				asset_b,
+				asset_a.into(),
+				asset_b.into(),
 				amount_a,
-				amount_b
+				amount_b,
+				0,
+				0,
+				None
 			));
 			let swap_amount = expand_to_decimals(60u128);
 			assert_noop!(
-				Dex::swap(RuntimeOrigin::signed(ALICE), asset_a, asset_b, swap_amount),
+				Dex::swap(
+					RuntimeOrigin::signed(ALICE),
+					asset_a.into(),
+					asset_b.into(),
+					swap_amount,
+					None
+				),
+				Error::<Test>::InsufficientLiquidity
+			);
+		});
+}
+
+#[test]
+fn swapping_exact_tokens_for_tokens_works() {
+	let asset_a: AssetId = 1001;
+	let asset_b: AssetId = 1002;
+	let total_a: u128 = expand_to_decimals(100u128);
+	let total_b: u128 = expand_to_decimals(100u128);
+	let amount_a: u128 = expand_to_decimals(10u128);
+	let amount_b: u128 = expand_to_decimals(10u128);
+
+	ExtBuilder::default()
+		.with_endowed_balances(vec![(asset_a, ALICE, total_a), (asset_b, ALICE, total_b)])
+		.build_and_execute(|| {
+			let swap_amount = expand_to_decimals(1u128);
+			let expected_amount_out: u128 = 9066108938;
+			assert_ok!(Dex::create_pool(
+				RuntimeOrigin::signed(ALICE),
+				asset_a.into(),
+				asset_b.into(),
+				DEFAULT_FEE_TIER
+			));
+			assert_ok!(Dex::mint(
+				RuntimeOrigin::signed(ALICE),
+				asset_a.into(),
+				asset_b.into(),
+				amount_a,
+				amount_b,
+				0,
+				0,
+				None
+			));
+			assert_ok!(Dex::swap_exact_tokens_for_tokens(
+				RuntimeOrigin::signed(ALICE),
+				asset_a.into(),
+				asset_b.into(),
+				swap_amount,
+				expected_amount_out,
+				None
+			));
+
+			let pool_key = AssetPair::new(asset_a.into(), asset_b.into());
+			let pool = LiquidityPools::<Test>::get(pool_key).unwrap();
+
+			// Internal balances should be updated
+			assert_eq!(pool.asset_a_balance, amount_a + swap_amount);
+			assert_eq!(pool.asset_b_balance, amount_b - expected_amount_out);
+
+			// User balances have been updated
+			assert_eq!(Fungibles::balance(asset_a, ALICE), total_a - amount_a - swap_amount);
+			assert_eq!(
+				Fungibles::balance(asset_b, ALICE),
+				total_b - amount_b + expected_amount_out
+			);
+
+			// Ensure correct events are triggered
+			frame_system::Pallet::<Test>::assert_has_event(RuntimeEvent::Dex(Event::Swapped {
+				who: ALICE,
+				pool_id: pool.id,
+				asset_in: asset_a.into(),
+				asset_out: asset_b.into(),
+				amount_in: swap_amount,
+				amount_out: expected_amount_out,
+				reserve_in: amount_a + swap_amount,
+				reserve_out: amount_b - expected_amount_out,
+			}));
+		});
+}
+
+#[test]
+fn swapping_exact_tokens_for_tokens_fails_on_insufficient_output_amount() {
+	let asset_a: AssetId = 1001;
+	let asset_b: AssetId = 1002;
+	let total_a: u128 = expand_to_decimals(100u128);
+	let total_b: u128 = expand_to_decimals(100u128);
+	let amount_a: u128 = expand_to_decimals(10u128);
+	let amount_b: u128 = expand_to_decimals(10u128);
+
+	ExtBuilder::default()
+		.with_endowed_balances(vec![(asset_a, ALICE, total_a), (asset_b, ALICE, total_b)])
+		.build_and_execute(|| {
+			let swap_amount = expand_to_decimals(1u128);
+			assert_ok!(Dex::create_pool(
+				RuntimeOrigin::signed(ALICE),
+				asset_a.into(),
+				asset_b.into(),
+				DEFAULT_FEE_TIER
+			));
+			assert_ok!(Dex::mint(
+				RuntimeOrigin::signed(ALICE),
+				asset_a.into(),
+				asset_b.into(),
+				amount_a,
+				amount_b,
+				0,
+				0,
+				None
+			));
+			assert_noop!(
+				Dex::swap_exact_tokens_for_tokens(
+					RuntimeOrigin::signed(ALICE),
+					asset_a.into(),
+					asset_b.into(),
+					swap_amount,
+					9066108939,
+					None
+				),
+				Error::<Test>::InsufficientOutputAmount
+			);
+		});
+}
+
+#[test]
+fn swapping_tokens_for_exact_tokens_works() {
+	let asset_a: AssetId = 1001;
+	let asset_b: AssetId = 1002;
+	let total_a: u128 = expand_to_decimals(100u128);
+	let total_b: u128 = expand_to_decimals(100u128);
+	let amount_a: u128 = expand_to_decimals(10u128);
+	let amount_b: u128 = expand_to_decimals(10u128);
+
+	ExtBuilder::default()
+		.with_endowed_balances(vec![(asset_a, ALICE, total_a), (asset_b, ALICE, total_b)])
+		.build_and_execute(|| {
+			let amount_out = expand_to_decimals(1u128);
+			let expected_amount_in: u128 = 11144544746;
+			assert_ok!(Dex::create_pool(
+				RuntimeOrigin::signed(ALICE),
+				asset_a.into(),
+				asset_b.into(),
+				DEFAULT_FEE_TIER
+			));
+			assert_ok!(Dex::mint(
+				RuntimeOrigin::signed(ALICE),
+				asset_a.into(),
+				asset_b.into(),
+				amount_a,
+				amount_b,
+				0,
+				0,
+				None
+			));
+			assert_ok!(Dex::swap_tokens_for_exact_tokens(
+				RuntimeOrigin::signed(ALICE),
+				asset_a.into(),
+				asset_b.into(),
+				amount_out,
+				expected_amount_in,
+				None
+			));
+
+			let pool_key = AssetPair::new(asset_a.into(), asset_b.into());
+			let pool = LiquidityPools::<Test>::get(pool_key).unwrap();
+
+			// Internal balances should be updated
+			assert_eq!(pool.asset_a_balance, amount_a + expected_amount_in);
+			assert_eq!(pool.asset_b_balance, amount_b - amount_out);
+
+			// User balances have been updated
+			assert_eq!(Fungibles::balance(asset_a, ALICE), total_a - amount_a - expected_amount_in);
+			assert_eq!(Fungibles::balance(asset_b, ALICE), total_b - amount_b + amount_out);
+
+			// Ensure correct events are triggered
+			frame_system::Pallet::<Test>::assert_has_event(RuntimeEvent::Dex(Event::Swapped {
+				who: ALICE,
+				pool_id: pool.id,
+				asset_in: asset_a.into(),
+				asset_out: asset_b.into(),
+				amount_in: expected_amount_in,
+				amount_out,
+				reserve_in: amount_a + expected_amount_in,
+				reserve_out: amount_b - amount_out,
+			}));
+		});
+}
+
+#[test]
+fn swapping_tokens_for_exact_tokens_fails_on_excessive_input_amount() {
+	let asset_a: AssetId = 1001;
+	let asset_b: AssetId = 1002;
+	let total_a: u128 = expand_to_decimals(100u128);
+	let total_b: u128 = expand_to_decimals(100u128);
+	let amount_a: u128 = expand_to_decimals(10u128);
+	let amount_b: u128 = expand_to_decimals(10u128);
+
+	ExtBuilder::default()
+		.with_endowed_balances(vec![(asset_a, ALICE, total_a), (asset_b, ALICE, total_b)])
+		.build_and_execute(|| {
+			assert_ok!(Dex::create_pool(
+				RuntimeOrigin::signed(ALICE),
+				asset_a.into(),
+				asset_b.into(),
+				DEFAULT_FEE_TIER
+			));
+			assert_ok!(Dex::mint(
+				RuntimeOrigin::signed(ALICE),
+				asset_a.into(),
+				asset_b.into(),
+				amount_a,
+				amount_b,
+				0,
+				0,
+				None
+			));
+			assert_noop!(
+				Dex::swap_tokens_for_exact_tokens(
+					RuntimeOrigin::signed(ALICE),
+					asset_a.into(),
+					asset_b.into(),
+					expand_to_decimals(1u128),
+					11144544745,
+					None
+				),
+				Error::<Test>::ExcessiveInputAmount
+			);
+		});
+}
+
+#[test]
+fn swapping_tokens_for_exact_tokens_fails_on_greater_than_pool_amount() {
+	let asset_a: AssetId = 1001;
+	let asset_b: AssetId = 1002;
+	let amount_a: u128 = expand_to_decimals(50u128);
+	let amount_b: u128 = expand_to_decimals(10u128);
+
+	ExtBuilder::default()
+		.with_endowed_balances(vec![(asset_a, ALICE, amount_a), (asset_b, ALICE, amount_b)])
+		.build_and_execute(|| {
+			assert_ok!(Dex::create_pool(
+				RuntimeOrigin::signed(ALICE),
+				asset_a.into(),
+				asset_b.into(),
+				DEFAULT_FEE_TIER
+			));
+			assert_ok!(Dex::mint(
+				RuntimeOrigin::signed(ALICE),
+				asset_a.into(),
+				asset_b.into(),
+				amount_a,
+				amount_b,
+				0,
+				0,
+				None
+			));
+			assert_noop!(
+				Dex::swap_tokens_for_exact_tokens(
+					RuntimeOrigin::signed(ALICE),
+					asset_a.into(),
+					asset_b.into(),
+					amount_b,
+					amount_a,
+					None
+				),
 				Error::<Test>::InsufficientLiquidity
 			);
 		});
 }
+
+#[test]
+fn swapping_along_path_works() {
+	let asset_a: AssetId = 1001;
+	let asset_b: AssetId = 1002;
+	let asset_c: AssetId = 1003;
+	let total: u128 = expand_to_decimals(100u128);
+	let amount: u128 = expand_to_decimals(10u128);
+
+	ExtBuilder::default()
+		.with_endowed_balances(vec![
+			(asset_a, ALICE, total),
+			(asset_b, ALICE, total),
+			(asset_c, ALICE, total),
+		])
+		.build_and_execute(|| {
+			let swap_amount = expand_to_decimals(1u128);
+			let expected_hop_amount: u128 = 9066108938;
+			let expected_amount_out: u128 = 8289619329;
+			assert_ok!(Dex::create_pool(
+				RuntimeOrigin::signed(ALICE),
+				asset_a.into(),
+				asset_b.into(),
+				DEFAULT_FEE_TIER
+			));
+			assert_ok!(Dex::mint(
+				RuntimeOrigin::signed(ALICE),
+				asset_a.into(),
+				asset_b.into(),
+				amount,
+				amount,
+				0,
+				0,
+				None
+			));
+			assert_ok!(Dex::create_pool(
+				RuntimeOrigin::signed(ALICE),
+				asset_b.into(),
+				asset_c.into(),
+				DEFAULT_FEE_TIER
+			));
+			assert_ok!(Dex::mint(
+				RuntimeOrigin::signed(ALICE),
+				asset_b.into(),
+				asset_c.into(),
+				amount,
+				amount,
+				0,
+				0,
+				None
+			));
+
+			let path =
+				BoundedVec::truncate_from(vec![asset_a.into(), asset_b.into(), asset_c.into()]);
+			assert_ok!(Dex::swap_along_path(
+				RuntimeOrigin::signed(ALICE),
+				path.clone(),
+				swap_amount,
+				expected_amount_out,
+				None
+			));
+
+			// Internal balances of every pool on the path should be updated
+			let first_pool =
+				LiquidityPools::<Test>::get(AssetPair::new(asset_a.into(), asset_b.into()))
+					.unwrap();
+			assert_eq!(first_pool.asset_a_balance, amount + swap_amount);
+			assert_eq!(first_pool.asset_b_balance, amount - expected_hop_amount);
+			let second_pool =
+				LiquidityPools::<Test>::get(AssetPair::new(asset_b.into(), asset_c.into()))
+					.unwrap();
+			assert_eq!(second_pool.asset_a_balance, amount + expected_hop_amount);
+			assert_eq!(second_pool.asset_b_balance, amount - expected_amount_out);
+
+			// User balances have been updated, the intermediate asset is passed through
+			assert_eq!(Fungibles::balance(asset_a, ALICE), total - amount - swap_amount);
+			assert_eq!(Fungibles::balance(asset_b, ALICE), total - 2 * amount);
+			assert_eq!(Fungibles::balance(asset_c, ALICE), total - amount + expected_amount_out);
+
+			// Ensure correct events are triggered
+			frame_system::Pallet::<Test>::assert_has_event(RuntimeEvent::Dex(Event::Swapped {
+				who: ALICE,
+				pool_id: first_pool.id,
+				asset_in: asset_a.into(),
+				asset_out: asset_b.into(),
+				amount_in: swap_amount,
+				amount_out: expected_hop_amount,
+				reserve_in: amount + swap_amount,
+				reserve_out: amount - expected_hop_amount,
+			}));
+			frame_system::Pallet::<Test>::assert_has_event(RuntimeEvent::Dex(Event::Swapped {
+				who: ALICE,
+				pool_id: second_pool.id,
+				asset_in: asset_b.into(),
+				asset_out: asset_c.into(),
+				amount_in: expected_hop_amount,
+				amount_out: expected_amount_out,
+				reserve_in: amount + expected_hop_amount,
+				reserve_out: amount - expected_amount_out,
+			}));
+			frame_system::Pallet::<Test>::assert_has_event(RuntimeEvent::Dex(Event::PathSwapped {
+				who: ALICE,
+				path,
+				amount_in: swap_amount,
+				amount_out: expected_amount_out,
+			}));
+		});
+}
+
+#[test]
+fn swapping_along_path_fails_on_insufficient_output_amount() {
+	let asset_a: AssetId = 1001;
+	let asset_b: AssetId = 1002;
+	let asset_c: AssetId = 1003;
+	let total: u128 = expand_to_decimals(100u128);
+	let amount: u128 = expand_to_decimals(10u128);
+
+	ExtBuilder::default()
+		.with_endowed_balances(vec![
+			(asset_a, ALICE, total),
+			(asset_b, ALICE, total),
+			(asset_c, ALICE, total),
+		])
+		.build_and_execute(|| {
+			assert_ok!(Dex::create_pool(
+				RuntimeOrigin::signed(ALICE),
+				asset_a.into(),
+				asset_b.into(),
+				DEFAULT_FEE_TIER
+			));
+			assert_ok!(Dex::mint(
+				RuntimeOrigin::signed(ALICE),
+				asset_a.into(),
+				asset_b.into(),
+				amount,
+				amount,
+				0,
+				0,
+				None
+			));
+			assert_ok!(Dex::create_pool(
+				RuntimeOrigin::signed(ALICE),
+				asset_b.into(),
+				asset_c.into(),
+				DEFAULT_FEE_TIER
+			));
+			assert_ok!(Dex::mint(
+				RuntimeOrigin::signed(ALICE),
+				asset_b.into(),
+				asset_c.into(),
+				amount,
+				amount,
+				0,
+				0,
+				None
+			));
+
+			// The first hop succeeds but has to be rolled back
+			assert_noop!(
+				Dex::swap_along_path(
+					RuntimeOrigin::signed(ALICE),
+					BoundedVec::truncate_from(vec![asset_a.into(), asset_b.into(), asset_c.into()]),
+					expand_to_decimals(1u128),
+					8289619330,
+					None
+				),
+				Error::<Test>::InsufficientOutputAmount
+			);
+		});
+}
+
+#[test]
+fn swapping_along_path_fails_on_missing_pool() {
+	let asset_a: AssetId = 1001;
+	let asset_b: AssetId = 1002;
+	let asset_c: AssetId = 1003;
+	let total: u128 = expand_to_decimals(100u128);
+	let amount: u128 = expand_to_decimals(10u128);
+
+	ExtBuilder::default()
+		.with_endowed_balances(vec![
+			(asset_a, ALICE, total),
+			(asset_b, ALICE, total),
+			(asset_c, ALICE, total),
+		])
+		.build_and_execute(|| {
+			assert_ok!(Dex::create_pool(
+				RuntimeOrigin::signed(ALICE),
+				asset_a.into(),
+				asset_b.into(),
+				DEFAULT_FEE_TIER
+			));
+			assert_ok!(Dex::mint(
+				RuntimeOrigin::signed(ALICE),
+				asset_a.into(),
+				asset_b.into(),
+				amount,
+				amount,
+				0,
+				0,
+				None
+			));
+			assert_noop!(
+				Dex::swap_along_path(
+					RuntimeOrigin::signed(ALICE),
+					BoundedVec::truncate_from(vec![asset_a.into(), asset_b.into(), asset_c.into()]),
+					expand_to_decimals(1u128),
+					0,
+					None
+				),
+				Error::<Test>::LiquidityPoolDoesNotExist
+			);
+		});
+}
+
+#[test]
+fn swapping_along_path_fails_on_invalid_path() {
+	let asset_a: AssetId = 1001;
+	let asset_b: AssetId = 1002;
+	let total: u128 = expand_to_decimals(100u128);
+
+	ExtBuilder::default()
+		.with_endowed_balances(vec![(asset_a, ALICE, total), (asset_b, ALICE, total)])
+		.build_and_execute(|| {
+			assert_noop!(
+				Dex::swap_along_path(
+					RuntimeOrigin::signed(ALICE),
+					BoundedVec::truncate_from(vec![asset_a.into()]),
+					expand_to_decimals(1u128),
+					0,
+					None
+				),
+				Error::<Test>::InvalidPath
+			);
+		});
+}
+
+#[test]
+fn swapping_fails_after_deadline() {
+	let asset_a: AssetId = 1001;
+	let asset_b: AssetId = 1002;
+	let total: u128 = expand_to_decimals(100u128);
+	let amount: u128 = expand_to_decimals(10u128);
+
+	ExtBuilder::default()
+		.with_endowed_balances(vec![(asset_a, ALICE, total), (asset_b, ALICE, total)])
+		.build_and_execute(|| {
+			let swap_amount = expand_to_decimals(1u128);
+			assert_ok!(Dex::create_pool(
+				RuntimeOrigin::signed(ALICE),
+				asset_a.into(),
+				asset_b.into(),
+				DEFAULT_FEE_TIER
+			));
+			assert_ok!(Dex::mint(
+				RuntimeOrigin::signed(ALICE),
+				asset_a.into(),
+				asset_b.into(),
+				amount,
+				amount,
+				0,
+				0,
+				None
+			));
+
+			// The deadline block itself is still valid
+			assert_ok!(Dex::swap(
+				RuntimeOrigin::signed(ALICE),
+				asset_a.into(),
+				asset_b.into(),
+				swap_amount,
+				Some(1)
+			));
+
+			System::set_block_number(2);
+			assert_noop!(
+				Dex::swap(
+					RuntimeOrigin::signed(ALICE),
+					asset_a.into(),
+					asset_b.into(),
+					swap_amount,
+					Some(1)
+				),
+				Error::<Test>::Expired
+			);
+			assert_noop!(
+				Dex::swap_exact_tokens_for_tokens(
+					RuntimeOrigin::signed(ALICE),
+					asset_a.into(),
+					asset_b.into(),
+					swap_amount,
+					0,
+					Some(1)
+				),
+				Error::<Test>::Expired
+			);
+			assert_noop!(
+				Dex::swap_tokens_for_exact_tokens(
+					RuntimeOrigin::signed(ALICE),
+					asset_a.into(),
+					asset_b.into(),
+					swap_amount,
+					total,
+					Some(1)
+				),
+				Error::<Test>::Expired
+			);
+		});
+}
+
+#[test]
+fn swapping_uses_pool_fee_tier() {
+	let asset_a: AssetId = 1001;
+	let asset_b: AssetId = 1002;
+	let total: u128 = expand_to_decimals(100u128);
+	let amount: u128 = expand_to_decimals(10u128);
+
+	ExtBuilder::default()
+		.with_endowed_balances(vec![(asset_a, ALICE, total), (asset_b, ALICE, total)])
+		.build_and_execute(|| {
+			let swap_amount = expand_to_decimals(1u128);
+			let expected_amount_out: u128 = 9008189262;
+			assert_ok!(Dex::create_pool(
+				RuntimeOrigin::signed(ALICE),
+				asset_a.into(),
+				asset_b.into(),
+				Permill::from_percent(1)
+			));
+			assert_ok!(Dex::mint(
+				RuntimeOrigin::signed(ALICE),
+				asset_a.into(),
+				asset_b.into(),
+				amount,
+				amount,
+				0,
+				0,
+				None
+			));
+
+			let pool = LiquidityPools::<Test>::get(AssetPair::new(asset_a.into(), asset_b.into()))
+				.unwrap();
+			assert_eq!(pool.fee, Permill::from_percent(1));
+
+			// Quotes and swaps both use the 1% fee of the pool
+			assert_eq!(
+				Dex::get_price_for(asset_a.into(), swap_amount, asset_b.into()),
+				Ok(expected_amount_out)
+			);
+			assert_ok!(Dex::swap_exact_tokens_for_tokens(
+				RuntimeOrigin::signed(ALICE),
+				asset_a.into(),
+				asset_b.into(),
+				swap_amount,
+				expected_amount_out,
+				None
+			));
+			assert_eq!(Fungibles::balance(asset_b, ALICE), total - amount + expected_amount_out);
+		});
+}
@@ -0,0 +1,217 @@
+use crate::tests::mock::*;
+use crate::types::AssetPair;
+use crate::{Error, Event, LiquidityPools, NativeOrAssetId};
+use frame_support::{assert_noop, assert_ok};
+use sp_runtime::traits::AccountIdConversion;
+
+/// Transfers `amount` of `asset` from `ALICE` straight to the account of the pool.
+fn donate(asset: AssetId, manager: AccountId, amount: u128) {
+	assert_ok!(Assets::transfer(RuntimeOrigin::signed(ALICE), asset.into(), manager, amount));
+}
+
+#[test]
+fn each_pool_has_its_own_account() {
+	let asset_a: AssetId = 1001;
+	let asset_b: AssetId = 1002;
+	let asset_c: AssetId = 1003;
+	let total: u128 = expand_to_decimals(100u128);
+	let amount: u128 = expand_to_decimals(10u128);
+
+	ExtBuilder::default()
+		.with_endowed_balances(vec![
+			(asset_a, ALICE, total),
+			(asset_b, ALICE, total),
+			(asset_c, ALICE, total),
+		])
+		.build_and_execute(|| {
+			create_pool_with_liquidity(asset_a, asset_b, amount, amount);
+			create_pool_with_liquidity(asset_b, asset_c, amount, amount);
+
+			let first_pool =
+				LiquidityPools::<Test>::get(AssetPair::new(asset_a.into(), asset_b.into()))
+					.unwrap();
+			let second_pool =
+				LiquidityPools::<Test>::get(AssetPair::new(asset_b.into(), asset_c.into()))
+					.unwrap();
+			assert_eq!(first_pool.manager, Dex::pool_account(first_pool.id));
+			assert_eq!(second_pool.manager, Dex::pool_account(second_pool.id));
+			assert_ne!(first_pool.manager, second_pool.manager);
+			let pallet_account: AccountId = MemeSwapPallet::get().into_account_truncating();
+			assert_ne!(first_pool.manager, pallet_account);
+
+			assert_eq!(Fungibles::balance(asset_b, first_pool.manager), amount);
+			assert_eq!(Fungibles::balance(asset_b, second_pool.manager), amount);
+		});
+}
+
+#[test]
+fn sync_adds_donations_to_the_reserves() {
+	let asset_a: AssetId = 1001;
+	let asset_b: AssetId = 1002;
+	let total: u128 = expand_to_decimals(100u128);
+	let amount: u128 = expand_to_decimals(10u128);
+	let donation: u128 = expand_to_decimals(1u128);
+
+	ExtBuilder::default()
+		.with_endowed_balances(vec![(asset_a, ALICE, total), (asset_b, ALICE, total)])
+		.build_and_execute(|| {
+			create_pool_with_liquidity(asset_a, asset_b, amount, amount);
+			let pool_key = AssetPair::new(asset_a.into(), asset_b.into());
+			let pool = LiquidityPools::<Test>::get(&pool_key).unwrap();
+			donate(asset_a, pool.manager, donation);
+
+			assert_ok!(Dex::sync(RuntimeOrigin::signed(BOB), asset_a.into(), asset_b.into()));
+
+			let pool = LiquidityPools::<Test>::get(&pool_key).unwrap();
+			assert_eq!(pool.asset_a_balance, amount + donation);
+			assert_eq!(pool.asset_b_balance, amount);
+			frame_system::Pallet::<Test>::assert_has_event(RuntimeEvent::Dex(Event::Synced {
+				who: BOB,
+				pool_id: pool.id,
+				asset_a: asset_a.into(),
+				asset_b: asset_b.into(),
+				reserve_a: amount + donation,
+				reserve_b: amount,
+			}));
+		});
+}
+
+#[test]
+fn sync_fails_on_pool_without_liquidity() {
+	let asset_a: AssetId = 1001;
+	let asset_b: AssetId = 1002;
+	let total: u128 = expand_to_decimals(100u128);
+
+	ExtBuilder::default()
+		.with_endowed_balances(vec![(asset_a, ALICE, total), (asset_b, ALICE, total)])
+		.build_and_execute(|| {
+			assert_ok!(Dex::create_pool(
+				RuntimeOrigin::signed(ALICE),
+				asset_a.into(),
+				asset_b.into(),
+				DEFAULT_FEE_TIER
+			));
+			let pool = LiquidityPools::<Test>::get(AssetPair::new(asset_a.into(), asset_b.into()))
+				.unwrap();
+			donate(asset_a, pool.manager, expand_to_decimals(1u128));
+			donate(asset_b, pool.manager, expand_to_decimals(1u128));
+
+			assert_noop!(
+				Dex::sync(RuntimeOrigin::signed(BOB), asset_a.into(), asset_b.into()),
+				Error::<Test>::InsufficientLiquidity
+			);
+		});
+}
+
+#[test]
+fn skim_sends_donations_out() {
+	let asset_a: AssetId = 1001;
+	let asset_b: AssetId = 1002;
+	let total: u128 = expand_to_decimals(100u128);
+	let amount: u128 = expand_to_decimals(10u128);
+	let donation: u128 = expand_to_decimals(1u128);
+
+	ExtBuilder::default()
+		.with_endowed_balances(vec![(asset_a, ALICE, total), (asset_b, ALICE, total)])
+		.build_and_execute(|| {
+			create_pool_with_liquidity(asset_a, asset_b, amount, amount);
+			let pool_key = AssetPair::new(asset_a.into(), asset_b.into());
+			let pool = LiquidityPools::<Test>::get(&pool_key).unwrap();
+			donate(asset_b, pool.manager, donation);
+
+			assert_ok!(Dex::skim(RuntimeOrigin::signed(BOB), asset_a.into(), asset_b.into(), BOB));
+
+			// The pool is left as it was
+			assert_eq!(LiquidityPools::<Test>::get(&pool_key).unwrap(), pool);
+			assert_eq!(Fungibles::balance(asset_b, pool.manager), amount);
+			assert_eq!(Fungibles::balance(asset_b, BOB), donation);
+			frame_system::Pallet::<Test>::assert_has_event(RuntimeEvent::Dex(Event::Skimmed {
+				who: BOB,
+				to: BOB,
+				pool_id: pool.id,
+				asset_a: asset_a.into(),
+				asset_b: asset_b.into(),
+				amount_a: 0,
+				amount_b: donation,
+			}));
+		});
+}
+
+#[test]
+fn sync_and_skim_fail_on_shared_pool_account() {
+	let asset_a: AssetId = 1001;
+	let asset_b: AssetId = 1002;
+	let total: u128 = expand_to_decimals(100u128);
+
+	ExtBuilder::default()
+		.with_endowed_balances(vec![(asset_a, ALICE, total), (asset_b, ALICE, total)])
+		.build_and_execute(|| {
+			assert_ok!(Dex::create_pool(
+				RuntimeOrigin::signed(ALICE),
+				asset_a.into(),
+				asset_b.into(),
+				DEFAULT_FEE_TIER
+			));
+			// Pools created before per pool accounts are managed by the pallet account
+			let pool_key = AssetPair::new(asset_a.into(), asset_b.into());
+			LiquidityPools::<Test>::mutate(&pool_key, |pool| {
+				pool.as_mut().unwrap().manager = MemeSwapPallet::get().into_account_truncating();
+			});
+
+			assert_noop!(
+				Dex::sync(RuntimeOrigin::signed(BOB), asset_a.into(), asset_b.into()),
+				Error::<Test>::SharedPoolAccount
+			);
+			assert_noop!(
+				Dex::skim(RuntimeOrigin::signed(BOB), asset_a.into(), asset_b.into(), BOB),
+				Error::<Test>::SharedPoolAccount
+			);
+		});
+}
+
+#[test]
+fn sync_and_skim_leave_the_existential_deposit_of_native_pools() {
+	let asset_b: AssetId = 1002;
+	let total: u128 = expand_to_decimals(100u128);
+	let amount: u128 = expand_to_decimals(10u128);
+
+	ExtBuilder::default()
+		.with_endowed_balances(vec![(asset_b, ALICE, total)])
+		.with_native_balances(vec![(ALICE, total)])
+		.build_and_execute(|| {
+			assert_ok!(Dex::create_pool(
+				RuntimeOrigin::signed(ALICE),
+				NativeOrAssetId::Native,
+				asset_b.into(),
+				DEFAULT_FEE_TIER
+			));
+			assert_ok!(Dex::mint(
+				RuntimeOrigin::signed(ALICE),
+				NativeOrAssetId::Native,
+				asset_b.into(),
+				amount,
+				amount,
+				0,
+				0,
+				None
+			));
+			let pool_key = AssetPair::new(NativeOrAssetId::Native, asset_b.into());
+			let pool = LiquidityPools::<Test>::get(&pool_key).unwrap();
+
+			// The existential deposit paid by the creator is neither skimmed nor synced
+			assert_ok!(Dex::skim(
+				RuntimeOrigin::signed(BOB),
+				NativeOrAssetId::Native,
+				asset_b.into(),
+				BOB
+			));
+			assert_eq!(Balances::free_balance(BOB), 0);
+			assert_ok!(Dex::sync(
+				RuntimeOrigin::signed(BOB),
+				NativeOrAssetId::Native,
+				asset_b.into()
+			));
+			assert_eq!(LiquidityPools::<Test>::get(&pool_key).unwrap().asset_a_balance, amount);
+			assert_eq!(Balances::free_balance(pool.manager), amount + EXISTENTIAL_DEPOSIT);
+		});
+}
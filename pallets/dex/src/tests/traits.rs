@@ -2,30 +2,36 @@ use crate::tests::mock::*;
 use crate::traits::{OraclePrice, TokenRatio};
 use crate::Error;
 use frame_support::{assert_noop, assert_ok};
-use sp_runtime::Perbill;
+use sp_runtime::{FixedPointNumber, FixedU128, Perbill};
 
 #[test]
 fn fetching_token_ratio_works_on_a_to_b() {
 	let asset_a: AssetId = 1001;
 	let asset_b: AssetId = 1002;
-	let pool_id: AssetId = 10000;
 	let amount_a: u128 = expand_to_decimals(10u128);
 	let amount_b: u128 = expand_to_decimals(50u128);
 
 	ExtBuilder::default()
 		.with_endowed_balances(vec![(asset_a, ALICE, amount_a), (asset_b, ALICE, amount_b)])
-		.build()
-		.execute_with(|| {
+		.build_and_execute(|| {
+			assert_ok!(Dex::create_pool(
+				RuntimeOrigin::signed(ALICE),
+				asset_a.into(),
+				asset_b.into(),
+				DEFAULT_FEE_TIER
+			));
 			assert_ok!(Dex::mint(
 				RuntimeOrigin::signed(ALICE),
-				pool_id,
-				asset_a,
-				asset_b,
+				asset_a.into(),
+				asset_b.into(),
 				amount_a,
 				amount_b,
+				0,
+				0,
+				None
 			));
 
-			assert_eq!(Dex::ratio(asset_a, asset_b), Ok(Perbill::from_percent(20)));
+			assert_eq!(Dex::ratio(asset_a.into(), asset_b.into()), Ok(Perbill::from_percent(20)));
 		});
 }
 
@@ -33,24 +39,30 @@ fn fetching_token_ratio_works_on_a_to_b() {
 fn fetching_token_ratio_works_on_b_to_a() {
 	let asset_a: AssetId = 1001;
 	let asset_b: AssetId = 1002;
-	let pool_id: AssetId = 10000;
 	let amount_a: u128 = expand_to_decimals(100u128);
 	let amount_b: u128 = expand_to_decimals(50u128);
 
 	ExtBuilder::default()
 		.with_endowed_balances(vec![(asset_a, ALICE, amount_a), (asset_b, ALICE, amount_b)])
-		.build()
-		.execute_with(|| {
+		.build_and_execute(|| {
+			assert_ok!(Dex::create_pool(
+				RuntimeOrigin::signed(ALICE),
+				asset_a.into(),
+				asset_b.into(),
+				DEFAULT_FEE_TIER
+			));
 			assert_ok!(Dex::mint(
 				RuntimeOrigin::signed(ALICE),
-				pool_id,
-				asset_a,
-				asset_b,
+				asset_a.into(),
+				asset_b.into(),
 				amount_a,
 				amount_b,
+				0,
+				0,
+				None
 			));
 
-			assert_eq!(Dex::ratio(asset_b, asset_a), Ok(Perbill::from_percent(50)));
+			assert_eq!(Dex::ratio(asset_b.into(), asset_a.into()), Ok(Perbill::from_percent(50)));
 		});
 }
 
@@ -58,23 +70,32 @@ fn fetching_token_ratio_works_on_b_to_a() {
 fn fetching_token_ratio_fails_on_identical_assets() {
 	let asset_a: AssetId = 1001;
 	let asset_b: AssetId = 1002;
-	let pool_id: AssetId = 10000;
 	let amount_a: u128 = expand_to_decimals(10u128);
 	let amount_b: u128 = expand_to_decimals(50u128);
 
 	ExtBuilder::default()
 		.with_endowed_balances(vec![(asset_a, ALICE, amount_a), (asset_b, ALICE, amount_b)])
-		.build()
-		.execute_with(|| {
+		.build_and_execute(|| {
+			assert_ok!(Dex::create_pool(
+				RuntimeOrigin::signed(ALICE),
+				asset_a.into(),
+				asset_b.into(),
+				DEFAULT_FEE_TIER
+			));
 			assert_ok!(Dex::mint(
 				RuntimeOrigin::signed(ALICE),
-				pool_id,
-				asset_a,
-				asset_b,
+				asset_a.into(),
+				asset_b.into(),
 				amount_a,
-				amount_b
+				amount_b,
+				0,
+				0,
+				None
 			));
-			assert_noop!(Dex::ratio(asset_a, asset_a), Error::<Test>::IdenticalAssets);
+			assert_noop!(
+				Dex::ratio(asset_a.into(), asset_a.into()),
+				Error::<Test>::IdenticalAssets
+			);
 		});
 }
 
@@ -87,9 +108,11 @@ fn fetching_token_ratio_fails_on_nonexistent_pool() {
 
 	ExtBuilder::default()
 		.with_endowed_balances(vec![(asset_a, ALICE, amount_a), (asset_b, ALICE, amount_b)])
-		.build()
-		.execute_with(|| {
-			assert_noop!(Dex::ratio(asset_a, asset_b), Error::<Test>::LiquidityPoolDoesNotExist);
+		.build_and_execute(|| {
+			assert_noop!(
+				Dex::ratio(asset_a.into(), asset_b.into()),
+				Error::<Test>::LiquidityPoolDoesNotExist
+			);
 		});
 }
 
@@ -101,9 +124,8 @@ fn fetching_token_ratio_fails_on_unknown_asset_a() {
 
 	ExtBuilder::default()
 		.with_endowed_balances(vec![(asset_b, ALICE, amount_b)])
-		.build()
-		.execute_with(|| {
-			assert_noop!(Dex::ratio(asset_a, asset_b), Error::<Test>::UnknownAssetId);
+		.build_and_execute(|| {
+			assert_noop!(Dex::ratio(asset_a.into(), asset_b.into()), Error::<Test>::UnknownAssetId);
 		});
 }
 
@@ -115,9 +137,127 @@ fn fetching_token_ratio_fails_on_unknown_asset_b() {
 
 	ExtBuilder::default()
 		.with_endowed_balances(vec![(asset_a, ALICE, amount_a)])
-		.build()
-		.execute_with(|| {
-			assert_noop!(Dex::ratio(asset_a, asset_b), Error::<Test>::UnknownAssetId);
+		.build_and_execute(|| {
+			assert_noop!(Dex::ratio(asset_a.into(), asset_b.into()), Error::<Test>::UnknownAssetId);
+		});
+}
+
+#[test]
+fn fetching_fixed_token_ratio_works_above_one() {
+	let asset_a: AssetId = 1001;
+	let asset_b: AssetId = 1002;
+	let amount_a: u128 = expand_to_decimals(1_000_000u128);
+	let amount_b: u128 = expand_to_decimals(1u128);
+
+	ExtBuilder::default()
+		.with_endowed_balances(vec![(asset_a, ALICE, amount_a), (asset_b, ALICE, amount_b)])
+		.build_and_execute(|| {
+			assert_ok!(Dex::create_pool(
+				RuntimeOrigin::signed(ALICE),
+				asset_a.into(),
+				asset_b.into(),
+				DEFAULT_FEE_TIER
+			));
+			assert_ok!(Dex::mint(
+				RuntimeOrigin::signed(ALICE),
+				asset_a.into(),
+				asset_b.into(),
+				amount_a,
+				amount_b,
+				0,
+				0,
+				None
+			));
+
+			// The Perbill ratio saturates while the fixed point one doesn't
+			assert_eq!(Dex::ratio(asset_a.into(), asset_b.into()), Ok(Perbill::from_percent(100)));
+			assert_eq!(
+				Dex::ratio_fixed(asset_a.into(), asset_b.into()),
+				Ok(FixedU128::saturating_from_integer(1_000_000u128))
+			);
+			assert_eq!(
+				Dex::ratio_fixed(asset_b.into(), asset_a.into()),
+				Ok(FixedU128::saturating_from_rational(1u128, 1_000_000u128))
+			);
+		});
+}
+
+#[test]
+fn fetching_fixed_token_ratio_adjusts_for_decimals() {
+	let asset_a: AssetId = 1001;
+	let asset_b: AssetId = 1002;
+	// 2 units of asset_a with 6 decimals and 1 unit of asset_b with 10 decimals
+	let amount_a: u128 = 2 * 10u128.pow(6);
+	let amount_b: u128 = 10u128.pow(10);
+
+	ExtBuilder::default()
+		.with_endowed_balances(vec![(asset_a, ALICE, amount_a), (asset_b, ALICE, amount_b)])
+		.build_and_execute(|| {
+			assert_ok!(Assets::force_set_metadata(
+				RuntimeOrigin::root(),
+				asset_a.into(),
+				b"Meme A".to_vec(),
+				b"MMA".to_vec(),
+				6,
+				false
+			));
+			assert_ok!(Assets::force_set_metadata(
+				RuntimeOrigin::root(),
+				asset_b.into(),
+				b"Meme B".to_vec(),
+				b"MMB".to_vec(),
+				10,
+				false
+			));
+			assert_ok!(Dex::create_pool(
+				RuntimeOrigin::signed(ALICE),
+				asset_a.into(),
+				asset_b.into(),
+				DEFAULT_FEE_TIER
+			));
+			assert_ok!(Dex::mint(
+				RuntimeOrigin::signed(ALICE),
+				asset_a.into(),
+				asset_b.into(),
+				amount_a,
+				amount_b,
+				0,
+				0,
+				None
+			));
+
+			assert_eq!(
+				Dex::ratio_fixed(asset_a.into(), asset_b.into()),
+				Ok(FixedU128::saturating_from_integer(2u128))
+			);
+			assert_eq!(
+				Dex::ratio_fixed(asset_b.into(), asset_a.into()),
+				Ok(FixedU128::saturating_from_rational(1u128, 2u128))
+			);
+		});
+}
+
+#[test]
+fn fetching_fixed_token_ratio_fails_on_empty_pool() {
+	let asset_a: AssetId = 1001;
+	let asset_b: AssetId = 1002;
+	let amount_a: u128 = expand_to_decimals(10u128);
+	let amount_b: u128 = expand_to_decimals(50u128);
+
+	ExtBuilder::default()
+		.with_endowed_balances(vec![(asset_a, ALICE, amount_a), (asset_b, ALICE, amount_b)])
+		.build_and_execute(|| {
+			assert_ok!(Dex::create_pool(
+				RuntimeOrigin::signed(ALICE),
+				asset_a.into(),
+				asset_b.into(),
+				DEFAULT_FEE_TIER
+			));
+
+			assert_noop!(
+				Dex::ratio_fixed(asset_a.into(), asset_b.into()),
+				Error::<Test>::InsufficientLiquidity
+			);
 		});
 }
 
@@ -125,24 +265,30 @@ fn fetching_token_ratio_fails_on_unknown_asset_b() {
 fn fetching_price_for_works_from_a_b() {
 	let asset_a: AssetId = 1001;
 	let asset_b: AssetId = 1002;
-	let pool_id: AssetId = 10000;
 	let amount_a: u128 = expand_to_decimals(10u128);
 	let amount_b: u128 = expand_to_decimals(50u128);
 
 	ExtBuilder::default()
 		.with_endowed_balances(vec![(asset_a, ALICE, amount_a), (asset_b, ALICE, amount_b)])
-		.build()
-		.execute_with(|| {
+		.build_and_execute(|| {
+			assert_ok!(Dex::create_pool(
+				RuntimeOrigin::signed(ALICE),
+				asset_a.into(),
+				asset_b.into(),
+				DEFAULT_FEE_TIER
+			));
 			assert_ok!(Dex::mint(
 				RuntimeOrigin::signed(ALICE),
-				pool_id,
-				asset_a,
-				asset_b,
+				asset_a.into(),
+				asset_b.into(),
 				amount_a,
 				amount_b,
+				0,
+				0,
+				None
 			));
 
-			assert_eq!(Dex::get_price_for(asset_a, 1, asset_b), Ok(4));
+			assert_eq!(Dex::get_price_for(asset_a.into(), 1, asset_b.into()), Ok(4));
 		});
 }
 
@@ -150,24 +296,30 @@ fn fetching_price_for_works_from_a_b() {
 fn fetching_price_for_works_from_b_a() {
 	let asset_a: AssetId = 1001;
 	let asset_b: AssetId = 1002;
-	let pool_id: AssetId = 10000;
 	let amount_a: u128 = expand_to_decimals(300u128);
 	let amount_b: u128 = expand_to_decimals(40u128);
 
 	ExtBuilder::default()
 		.with_endowed_balances(vec![(asset_a, ALICE, amount_a), (asset_b, ALICE, amount_b)])
-		.build()
-		.execute_with(|| {
+		.build_and_execute(|| {
+			assert_ok!(Dex::create_pool(
+				RuntimeOrigin::signed(ALICE),
+				asset_a.into(),
+				asset_b.into(),
+				DEFAULT_FEE_TIER
+			));
 			assert_ok!(Dex::mint(
 				RuntimeOrigin::signed(ALICE),
-				pool_id,
-				asset_a,
-				asset_b,
+				asset_a.into(),
+				asset_b.into(),
 				amount_a,
 				amount_b,
+				0,
+				0,
+				None
 			));
 
-			assert_eq!(Dex::get_price_for(asset_b, 1, asset_a), Ok(7));
+			assert_eq!(Dex::get_price_for(asset_b.into(), 1, asset_a.into()), Ok(7));
 		});
 }
 
@@ -175,23 +327,32 @@ fn fetching_price_for_works_from_b_a() {
 fn fetching_price_fails_on_identical_assets() {
 	let asset_a: AssetId = 1001;
 	let asset_b: AssetId = 1002;
-	let pool_id: AssetId = 10000;
 	let amount_a: u128 = expand_to_decimals(10u128);
 	let amount_b: u128 = expand_to_decimals(50u128);
 
 	ExtBuilder::default()
 		.with_endowed_balances(vec![(asset_a, ALICE, amount_a), (asset_b, ALICE, amount_b)])
-		.build()
-		.execute_with(|| {
+		.build_and_execute(|| {
+			assert_ok!(Dex::create_pool(
+				RuntimeOrigin::signed(ALICE),
+				asset_a.into(),
+				asset_b.into(),
+				DEFAULT_FEE_TIER
+			));
 			assert_ok!(Dex::mint(
 				RuntimeOrigin::signed(ALICE),
-				pool_id,
-				asset_a,
-				asset_b,
+				asset_a.into(),
+				asset_b.into(),
 				amount_a,
-				amount_b
+				amount_b,
+				0,
+				0,
+				None
 			));
-			assert_noop!(Dex::get_price_for(asset_a, 1, asset_a), Error::<Test>::IdenticalAssets);
+			assert_noop!(
+				Dex::get_price_for(asset_a.into(), 1, asset_a.into()),
+				Error::<Test>::IdenticalAssets
+			);
 		});
 }
 
@@ -204,10 +365,9 @@ fn fetching_price_fails_on_nonexistent_pool() {
 
 	ExtBuilder::default()
 		.with_endowed_balances(vec![(asset_a, ALICE, amount_a), (asset_b, ALICE, amount_b)])
-		.build()
-		.execute_with(|| {
+		.build_and_execute(|| {
 			assert_noop!(
-				Dex::get_price_for(asset_a, 1, asset_b),
+				Dex::get_price_for(asset_a.into(), 1, asset_b.into()),
 				Error::<Test>::LiquidityPoolDoesNotExist
 			);
 		});
@@ -221,9 +381,11 @@ fn fetching_price_fails_on_unknown_asset_a() {
 
 	ExtBuilder::default()
 		.with_endowed_balances(vec![(asset_b, ALICE, amount_b)])
-		.build()
-		.execute_with(|| {
-			assert_noop!(Dex::get_price_for(asset_a, 1, asset_b), Error::<Test>::UnknownAssetId);
+		.build_and_execute(|| {
+			assert_noop!(
+				Dex::get_price_for(asset_a.into(), 1, asset_b.into()),
+				Error::<Test>::UnknownAssetId
+			);
 		});
 }
 
@@ -235,8 +397,302 @@ fn fetching_price_fails_on_unknown_asset_b() {
 
 	ExtBuilder::default()
 		.with_endowed_balances(vec![(asset_a, ALICE, amount_a)])
-		.build()
-		.execute_with(|| {
-			assert_noop!(Dex::get_price_for(asset_a, 1, asset_b), Error::<Test>::UnknownAssetId);
+		.build_and_execute(|| {
+			assert_noop!(
+				Dex::get_price_for(asset_a.into(), 1, asset_b.into()),
+				Error::<Test>::UnknownAssetId
+			);
+		});
+}
+
+#[test]
+fn fetching_input_for_works_from_a_b() {
+	let asset_a: AssetId = 1001;
+	let asset_b: AssetId = 1002;
+	let amount_a: u128 = expand_to_decimals(10u128);
+	let amount_b: u128 = expand_to_decimals(50u128);
+
+	ExtBuilder::default()
+		.with_endowed_balances(vec![(asset_a, ALICE, amount_a), (asset_b, ALICE, amount_b)])
+		.build_and_execute(|| {
+			assert_ok!(Dex::create_pool(
+				RuntimeOrigin::signed(ALICE),
+				asset_a.into(),
+				asset_b.into(),
+				DEFAULT_FEE_TIER
+			));
+			assert_ok!(Dex::mint(
+				RuntimeOrigin::signed(ALICE),
+				asset_a.into(),
+				asset_b.into(),
+				amount_a,
+				amount_b,
+				0,
+				0,
+				None
+			));
+
+			assert_eq!(
+				Dex::get_input_for(asset_a.into(), asset_b.into(), expand_to_decimals(1u128)),
+				Ok(2046957199)
+			);
+		});
+}
+
+#[test]
+fn fetching_price_and_input_for_works_on_large_reserves() {
+	let asset_a: AssetId = 1001;
+	let asset_b: AssetId = 1002;
+	// Like the demo pools, where the input, the fee and the output reserve multiplied overflow
+	let amount_a: u128 = 1 << 56;
+	let amount_b: u128 = 1 << 58;
+	let amount_in: u128 = 1 << 52;
+
+	ExtBuilder::default()
+		.with_endowed_balances(vec![(asset_a, ALICE, amount_a), (asset_b, ALICE, amount_b)])
+		.build_and_execute(|| {
+			create_pool_with_liquidity(asset_a, asset_b, amount_a, amount_b);
+
+			assert_eq!(
+				Dex::get_price_for(asset_a.into(), amount_in, asset_b.into()),
+				Ok(16906847386200894)
+			);
+			assert_eq!(
+				Dex::get_input_for(asset_a.into(), asset_b.into(), 16906847386200894),
+				Ok(amount_in)
+			);
+		});
+}
+
+#[test]
+fn fetching_input_for_fails_on_nonexistent_pool() {
+	let asset_a: AssetId = 1001;
+	let asset_b: AssetId = 1002;
+
+	ExtBuilder::default()
+		.with_endowed_balances(vec![(asset_a, ALICE, 1), (asset_b, ALICE, 1)])
+		.build_and_execute(|| {
+			assert_noop!(
+				Dex::get_input_for(asset_a.into(), asset_b.into(), 1),
+				Error::<Test>::LiquidityPoolDoesNotExist
+			);
+		});
+}
+
+#[test]
+fn fetching_reserves_works_in_given_order() {
+	let asset_a: AssetId = 1001;
+	let asset_b: AssetId = 1002;
+	let amount_a: u128 = expand_to_decimals(10u128);
+	let amount_b: u128 = expand_to_decimals(50u128);
+
+	ExtBuilder::default()
+		.with_endowed_balances(vec![(asset_a, ALICE, amount_a), (asset_b, ALICE, amount_b)])
+		.build_and_execute(|| {
+			assert_ok!(Dex::create_pool(
+				RuntimeOrigin::signed(ALICE),
+				asset_a.into(),
+				asset_b.into(),
+				DEFAULT_FEE_TIER
+			));
+			assert_ok!(Dex::mint(
+				RuntimeOrigin::signed(ALICE),
+				asset_a.into(),
+				asset_b.into(),
+				amount_a,
+				amount_b,
+				0,
+				0,
+				None
+			));
+
+			assert_eq!(Dex::get_reserves(asset_a.into(), asset_b.into()), Ok((amount_a, amount_b)));
+			assert_eq!(Dex::get_reserves(asset_b.into(), asset_a.into()), Ok((amount_b, amount_a)));
+		});
+}
+
+#[test]
+fn finding_best_route_works_on_direct_pool() {
+	let asset_a: AssetId = 1001;
+	let asset_b: AssetId = 1002;
+	let amount_a: u128 = expand_to_decimals(10u128);
+	let amount_b: u128 = expand_to_decimals(50u128);
+
+	ExtBuilder::default()
+		.with_endowed_balances(vec![(asset_a, ALICE, amount_a), (asset_b, ALICE, amount_b)])
+		.build_and_execute(|| {
+			assert_ok!(Dex::create_pool(
+				RuntimeOrigin::signed(ALICE),
+				asset_a.into(),
+				asset_b.into(),
+				DEFAULT_FEE_TIER
+			));
+			assert_ok!(Dex::mint(
+				RuntimeOrigin::signed(ALICE),
+				asset_a.into(),
+				asset_b.into(),
+				amount_a,
+				amount_b,
+				0,
+				0,
+				None
+			));
+
+			assert_eq!(
+				Dex::find_best_route(asset_a.into(), 1, asset_b.into(), 3),
+				Ok((vec![asset_a.into(), asset_b.into()], 4))
+			);
+		});
+}
+
+#[test]
+fn finding_best_route_prefers_higher_output_through_intermediate_pool() {
+	let asset_a: AssetId = 1001;
+	let asset_b: AssetId = 1002;
+	let asset_c: AssetId = 1003;
+	let total: u128 = expand_to_decimals(1000u128);
+	let shallow_amount: u128 = expand_to_decimals(10u128);
+	let deep_amount: u128 = expand_to_decimals(100u128);
+
+	ExtBuilder::default()
+		.with_endowed_balances(vec![
+			(asset_a, ALICE, total),
+			(asset_b, ALICE, total),
+			(asset_c, ALICE, total),
+		])
+		.build_and_execute(|| {
+			assert_ok!(Dex::create_pool(
+				RuntimeOrigin::signed(ALICE),
+				asset_a.into(),
+				asset_b.into(),
+				DEFAULT_FEE_TIER
+			));
+			assert_ok!(Dex::mint(
+				RuntimeOrigin::signed(ALICE),
+				asset_a.into(),
+				asset_b.into(),
+				shallow_amount,
+				shallow_amount,
+				0,
+				0,
+				None
+			));
+			assert_ok!(Dex::create_pool(
+				RuntimeOrigin::signed(ALICE),
+				asset_a.into(),
+				asset_c.into(),
+				DEFAULT_FEE_TIER
+			));
+			assert_ok!(Dex::mint(
+				RuntimeOrigin::signed(ALICE),
+				asset_a.into(),
+				asset_c.into(),
+				deep_amount,
+				deep_amount,
+				0,
+				0,
+				None
+			));
+			assert_ok!(Dex::create_pool(
+				RuntimeOrigin::signed(ALICE),
+				asset_c.into(),
+				asset_b.into(),
+				DEFAULT_FEE_TIER
+			));
+			assert_ok!(Dex::mint(
+				RuntimeOrigin::signed(ALICE),
+				asset_c.into(),
+				asset_b.into(),
+				deep_amount,
+				deep_amount,
+				0,
+				0,
+				None
+			));
+			let amount_in = expand_to_decimals(5u128);
+
+			// Only the direct pool is considered with a single hop
+			assert_eq!(
+				Dex::find_best_route(asset_a.into(), amount_in, asset_b.into(), 1),
+				Ok((vec![asset_a.into(), asset_b.into()], 33266599933))
+			);
+			// The deeper pools give a better output even with the extra fee
+			assert_eq!(
+				Dex::find_best_route(asset_a.into(), amount_in, asset_b.into(), 2),
+				Ok((vec![asset_a.into(), asset_c.into(), asset_b.into()], 45200699976))
+			);
+		});
+}
+
+#[test]
+fn finding_best_route_fails_on_disconnected_assets() {
+	let asset_a: AssetId = 1001;
+	let asset_b: AssetId = 1002;
+	let asset_c: AssetId = 1003;
+	let amount: u128 = expand_to_decimals(10u128);
+
+	ExtBuilder::default()
+		.with_endowed_balances(vec![
+			(asset_a, ALICE, amount),
+			(asset_b, ALICE, amount),
+			(asset_c, ALICE, amount),
+		])
+		.build_and_execute(|| {
+			assert_ok!(Dex::create_pool(
+				RuntimeOrigin::signed(ALICE),
+				asset_a.into(),
+				asset_b.into(),
+				DEFAULT_FEE_TIER
+			));
+			assert_ok!(Dex::mint(
+				RuntimeOrigin::signed(ALICE),
+				asset_a.into(),
+				asset_b.into(),
+				amount,
+				amount,
+				0,
+				0,
+				None
+			));
+
+			assert_noop!(
+				Dex::find_best_route(asset_a.into(), 1, asset_c.into(), 3),
+				Error::<Test>::NoRouteFound
+			);
+		});
+}
+
+#[test]
+fn oracle_best_route_only_searches_max_pools_scanned() {
+	let asset_a: AssetId = 1001;
+	let asset_b: AssetId = 1002;
+	let asset_c: AssetId = 1003;
+	let asset_d: AssetId = 1004;
+	let total: u128 = expand_to_decimals(100u128);
+	let amount: u128 = expand_to_decimals(10u128);
+
+	ExtBuilder::default()
+		.with_endowed_balances(vec![
+			(asset_a, ALICE, total),
+			(asset_b, ALICE, total),
+			(asset_c, ALICE, total),
+			(asset_d, ALICE, total),
+		])
+		.build_and_execute(|| {
+			let amount_in = expand_to_decimals(1u128);
+			create_pool_with_liquidity(asset_a, asset_b, amount, amount);
+			create_pool_with_liquidity(asset_b, asset_c, amount, amount);
+			assert_eq!(
+				<Dex as OraclePrice>::find_best_route(asset_a.into(), amount_in, asset_c.into(), 2),
+				Dex::find_best_route(asset_a.into(), amount_in, asset_c.into(), 2)
+			);
+
+			// The route to `asset_d` needs all three pools, but only `MaxPoolsScanned` are read
+			create_pool_with_liquidity(asset_c, asset_d, amount, amount);
+			assert_ok!(Dex::find_best_route(asset_a.into(), amount_in, asset_d.into(), 3));
+			assert_noop!(
+				<Dex as OraclePrice>::find_best_route(asset_a.into(), amount_in, asset_d.into(), 3),
+				Error::<Test>::NoRouteFound
+			);
 		});
 }
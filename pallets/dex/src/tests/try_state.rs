@@ -0,0 +1,105 @@
+use crate::tests::mock::*;
+use crate::types::AssetPair;
+use crate::{FlashSwapLock, LiquidityPools};
+use frame_support::assert_ok;
+use sp_runtime::DispatchError;
+
+#[test]
+fn try_state_allows_donations_to_the_manager() {
+	let asset_a: AssetId = 1001;
+	let asset_b: AssetId = 1002;
+	let total: u128 = expand_to_decimals(100u128);
+
+	ExtBuilder::default()
+		.with_endowed_balances(vec![(asset_a, ALICE, total), (asset_b, ALICE, total)])
+		.build_and_execute(|| {
+			create_pool_with_liquidity(
+				asset_a,
+				asset_b,
+				expand_to_decimals(10u128),
+				expand_to_decimals(10u128),
+			);
+			let pool_key = AssetPair::new(asset_a.into(), asset_b.into());
+			let pool = LiquidityPools::<Test>::get(pool_key).unwrap();
+
+			assert_ok!(Assets::transfer(
+				RuntimeOrigin::signed(ALICE),
+				asset_a.into(),
+				pool.manager,
+				expand_to_decimals(1u128)
+			));
+			assert_ok!(Dex::do_try_state());
+		});
+}
+
+#[test]
+fn try_state_fails_on_reserves_not_held_by_the_manager() {
+	let asset_a: AssetId = 1001;
+	let asset_b: AssetId = 1002;
+	let total: u128 = expand_to_decimals(100u128);
+
+	ExtBuilder::default()
+		.with_endowed_balances(vec![(asset_a, ALICE, total), (asset_b, ALICE, total)])
+		.build()
+		.execute_with(|| {
+			create_pool_with_liquidity(
+				asset_a,
+				asset_b,
+				expand_to_decimals(10u128),
+				expand_to_decimals(10u128),
+			);
+			let pool_key = AssetPair::new(asset_a.into(), asset_b.into());
+			LiquidityPools::<Test>::mutate(pool_key, |pool| {
+				pool.as_mut().unwrap().asset_b_balance += 1;
+			});
+
+			assert_eq!(
+				Dex::do_try_state(),
+				Err(DispatchError::Other(
+					"The reserves of the pools exceed the balance of their manager"
+				))
+			);
+		});
+}
+
+#[test]
+fn try_state_fails_on_lp_tokens_without_reserves() {
+	let asset_a: AssetId = 1001;
+	let asset_b: AssetId = 1002;
+	let total: u128 = expand_to_decimals(100u128);
+
+	ExtBuilder::default()
+		.with_endowed_balances(vec![(asset_a, ALICE, total), (asset_b, ALICE, total)])
+		.build()
+		.execute_with(|| {
+			create_pool_with_liquidity(
+				asset_a,
+				asset_b,
+				expand_to_decimals(10u128),
+				expand_to_decimals(10u128),
+			);
+			let pool_key = AssetPair::new(asset_a.into(), asset_b.into());
+			LiquidityPools::<Test>::mutate(pool_key, |pool| {
+				let pool = pool.as_mut().unwrap();
+				pool.asset_a_balance = 0;
+				pool.asset_b_balance = 0;
+			});
+
+			assert_eq!(
+				Dex::do_try_state(),
+				Err(DispatchError::Other("A pool with LP tokens has no reserves"))
+			);
+		});
+}
+
+#[test]
+fn try_state_fails_on_a_flash_swap_left_in_progress() {
+	ExtBuilder::default().build().execute_with(|| {
+		FlashSwapLock::<Test>::put(true);
+
+		assert_eq!(
+			Dex::do_try_state(),
+			Err(DispatchError::Other("A flash swap was left in progress"))
+		);
+	});
+}
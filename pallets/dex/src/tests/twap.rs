@@ -0,0 +1,190 @@
+use crate::tests::mock::*;
+use crate::traits::TwapOracle;
+use crate::types::AssetPair;
+use crate::{Error, LiquidityPools, PriceObservations};
+use frame_support::{assert_noop, assert_ok};
+use sp_runtime::{FixedPointNumber, FixedU128};
+
+#[test]
+fn consult_is_not_moved_by_a_swap_in_the_same_block() {
+	let asset_a: AssetId = 1001;
+	let asset_b: AssetId = 1002;
+	let total: u128 = expand_to_decimals(100u128);
+
+	ExtBuilder::default()
+		.with_endowed_balances(vec![(asset_a, ALICE, total), (asset_b, ALICE, total)])
+		.build_and_execute(|| {
+			create_pool_with_liquidity(
+				asset_a,
+				asset_b,
+				expand_to_decimals(10u128),
+				expand_to_decimals(40u128),
+			);
+
+			// Record an observation without moving the price
+			advance_time(OBSERVATION_PERIOD);
+			assert_ok!(Dex::mint(
+				RuntimeOrigin::signed(ALICE),
+				asset_a.into(),
+				asset_b.into(),
+				expand_to_decimals(1u128),
+				expand_to_decimals(4u128),
+				0,
+				0,
+				None
+			));
+
+			// A large swap moves the spot price but not the average up to this block
+			advance_time(OBSERVATION_PERIOD);
+			assert_ok!(Dex::swap(
+				RuntimeOrigin::signed(ALICE),
+				asset_a.into(),
+				asset_b.into(),
+				expand_to_decimals(20u128),
+				None
+			));
+			assert_eq!(
+				Dex::consult(asset_a.into(), asset_b.into(), OBSERVATION_PERIOD),
+				Ok(FixedU128::saturating_from_integer(4u128))
+			);
+			assert_eq!(
+				Dex::consult(asset_b.into(), asset_a.into(), OBSERVATION_PERIOD),
+				Ok(FixedU128::saturating_from_rational(1u128, 4u128))
+			);
+		});
+}
+
+#[test]
+fn consult_averages_prices_over_the_window() {
+	let asset_a: AssetId = 1001;
+	let asset_b: AssetId = 1002;
+	let total: u128 = expand_to_decimals(100u128);
+
+	ExtBuilder::default()
+		.with_endowed_balances(vec![(asset_a, ALICE, total), (asset_b, ALICE, total)])
+		.build_and_execute(|| {
+			create_pool_with_liquidity(
+				asset_a,
+				asset_b,
+				expand_to_decimals(10u128),
+				expand_to_decimals(40u128),
+			);
+			advance_time(OBSERVATION_PERIOD);
+			assert_ok!(Dex::mint(
+				RuntimeOrigin::signed(ALICE),
+				asset_a.into(),
+				asset_b.into(),
+				expand_to_decimals(1u128),
+				expand_to_decimals(4u128),
+				0,
+				0,
+				None
+			));
+			advance_time(OBSERVATION_PERIOD);
+			assert_ok!(Dex::swap(
+				RuntimeOrigin::signed(ALICE),
+				asset_a.into(),
+				asset_b.into(),
+				expand_to_decimals(20u128),
+				None
+			));
+			advance_time(OBSERVATION_PERIOD);
+
+			// The price was 4 for the first half of the window and the spot price after the
+			// swap for the second half
+			let pool_key = AssetPair::new(asset_a.into(), asset_b.into());
+			let pool = LiquidityPools::<Test>::get(pool_key).unwrap();
+			let spot_price =
+				FixedU128::checked_from_rational(pool.asset_b_balance, pool.asset_a_balance)
+					.unwrap();
+			let initial_price = FixedU128::saturating_from_integer(4u128);
+			let expected_price =
+				FixedU128::from_inner((initial_price.into_inner() + spot_price.into_inner()) / 2);
+			assert_eq!(
+				Dex::consult(asset_a.into(), asset_b.into(), 2 * OBSERVATION_PERIOD),
+				Ok(expected_price)
+			);
+		});
+}
+
+#[test]
+fn consult_fails_without_observation_old_enough() {
+	let asset_a: AssetId = 1001;
+	let asset_b: AssetId = 1002;
+	let total: u128 = expand_to_decimals(100u128);
+
+	ExtBuilder::default()
+		.with_endowed_balances(vec![(asset_a, ALICE, total), (asset_b, ALICE, total)])
+		.build_and_execute(|| {
+			create_pool_with_liquidity(
+				asset_a,
+				asset_b,
+				expand_to_decimals(10u128),
+				expand_to_decimals(40u128),
+			);
+			assert_noop!(
+				Dex::consult(asset_a.into(), asset_b.into(), OBSERVATION_PERIOD),
+				Error::<Test>::NoPriceObservation
+			);
+
+			advance_time(OBSERVATION_PERIOD);
+			assert_ok!(Dex::swap(
+				RuntimeOrigin::signed(ALICE),
+				asset_a.into(),
+				asset_b.into(),
+				expand_to_decimals(1u128),
+				None
+			));
+			assert_noop!(
+				Dex::consult(asset_a.into(), asset_b.into(), OBSERVATION_PERIOD),
+				Error::<Test>::NoPriceObservation
+			);
+		});
+}
+
+#[test]
+fn price_observations_are_recorded_once_per_period() {
+	let asset_a: AssetId = 1001;
+	let asset_b: AssetId = 1002;
+	let total: u128 = expand_to_decimals(100u128);
+
+	ExtBuilder::default()
+		.with_endowed_balances(vec![(asset_a, ALICE, total), (asset_b, ALICE, total)])
+		.build_and_execute(|| {
+			create_pool_with_liquidity(
+				asset_a,
+				asset_b,
+				expand_to_decimals(10u128),
+				expand_to_decimals(40u128),
+			);
+			let pool_key = AssetPair::new(asset_a.into(), asset_b.into());
+
+			for _ in 0..6 {
+				advance_time(OBSERVATION_PERIOD);
+				assert_ok!(Dex::swap(
+					RuntimeOrigin::signed(ALICE),
+					asset_a.into(),
+					asset_b.into(),
+					expand_to_decimals(1u128),
+					None
+				));
+				// Touching the pool again before the period is over records nothing
+				advance_time(OBSERVATION_PERIOD / 2);
+				assert_ok!(Dex::swap(
+					RuntimeOrigin::signed(ALICE),
+					asset_b.into(),
+					asset_a.into(),
+					expand_to_decimals(1u128),
+					None
+				));
+			}
+
+			// Only the latest `MaxObservations` are kept
+			let observations = PriceObservations::<Test>::get(&pool_key);
+			let timestamps: Vec<u64> =
+				observations.iter().map(|observation| observation.timestamp).collect();
+			let step = OBSERVATION_PERIOD + OBSERVATION_PERIOD / 2;
+			let expected: Vec<u64> = (3..=6).map(|i| i * step - OBSERVATION_PERIOD / 2).collect();
+			assert_eq!(timestamps, expected);
+		});
+}
@@ -1,9 +1,20 @@
-use sp_runtime::DispatchError;
-pub use sp_runtime::Perbill;
+use frame_support::weights::Weight;
+use sp_runtime::{DispatchError, DispatchResult};
+pub use sp_runtime::{FixedU128, Perbill};
+use sp_std::vec::Vec;
 
 pub trait TokenRatio {
 	type AssetId;
+	/// Returns the ratio of the reserves of `token_a` to `token_b`, which saturates at 100%.
+	/// Prefer `ratio_fixed` for pools where `token_a` has the larger reserve.
 	fn ratio(token_a: Self::AssetId, token_b: Self::AssetId) -> Result<Perbill, DispatchError>;
+
+	/// Returns the ratio of the reserves of `token_a` to `token_b` in whole units of each token,
+	/// adjusted for their decimals. Any ratio can be represented.
+	fn ratio_fixed(
+		token_a: Self::AssetId,
+		token_b: Self::AssetId,
+	) -> Result<FixedU128, DispatchError>;
 }
 
 pub trait OraclePrice {
@@ -15,4 +26,76 @@ pub trait OraclePrice {
 		amount_in: Self::Balance,
 		asset_out: Self::AssetId,
 	) -> Result<Self::Balance, DispatchError>;
+
+	/// Returns the amount of `asset_in` needed to get exactly `amount_out` of `asset_out`.
+	fn get_input_for(
+		asset_in: Self::AssetId,
+		asset_out: Self::AssetId,
+		amount_out: Self::Balance,
+	) -> Result<Self::Balance, DispatchError>;
+
+	/// Finds the path of at most `max_hops` pools from `asset_in` to `asset_out` that gives the
+	/// highest output for `amount_in`, along with that output amount. Only a bounded number of
+	/// pools is searched, so a route through the others may be missed.
+	fn find_best_route(
+		asset_in: Self::AssetId,
+		amount_in: Self::Balance,
+		asset_out: Self::AssetId,
+		max_hops: u32,
+	) -> Result<(Vec<Self::AssetId>, Self::Balance), DispatchError>;
+
+	/// Upper bound of the weight of `find_best_route`, for callers to charge in their own
+	/// dispatchables.
+	fn find_best_route_weight() -> Weight;
+}
+
+/// Manipulation resistant prices of the liquidity pools, averaged over time from price
+/// accumulators like the Uniswap V2 oracle, unlike the spot prices of `OraclePrice`.
+pub trait TwapOracle {
+	type AssetId;
+
+	/// Returns the time-weighted average price of `asset_in` in units of `asset_out` over at
+	/// least the last `window` seconds.
+	fn consult(
+		asset_in: Self::AssetId,
+		asset_out: Self::AssetId,
+		window: u64,
+	) -> Result<FixedU128, DispatchError>;
+}
+
+/// Pays for the output of a flash swap after receiving it, like `uniswapV2Call` of Uniswap V2.
+/// Before returning, the implementation has to transfer either asset to `pool_account` so that
+/// the constant product of the pool including the swap fee holds, otherwise the whole flash
+/// swap is reverted.
+pub trait FlashSwapCallback<AccountId, AssetId, Balance> {
+	/// Called after `amount_a_out` of `asset_a` and `amount_b_out` of `asset_b` were sent to
+	/// `who` from `pool_account`.
+	fn on_flash_swap(
+		who: &AccountId,
+		pool_account: &AccountId,
+		asset_a: AssetId,
+		asset_b: AssetId,
+		amount_a_out: Balance,
+		amount_b_out: Balance,
+	) -> DispatchResult;
+
+	/// Weight of `on_flash_swap`, charged on top of the flash swap itself.
+	fn weight() -> Weight;
+}
+
+impl<AccountId, AssetId, Balance> FlashSwapCallback<AccountId, AssetId, Balance> for () {
+	fn on_flash_swap(
+		_who: &AccountId,
+		_pool_account: &AccountId,
+		_asset_a: AssetId,
+		_asset_b: AssetId,
+		_amount_a_out: Balance,
+		_amount_b_out: Balance,
+	) -> DispatchResult {
+		Ok(())
+	}
+
+	fn weight() -> Weight {
+		Weight::zero()
+	}
 }
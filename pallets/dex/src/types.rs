@@ -1,16 +1,51 @@
 use super::*;
-use sp_runtime::traits::IntegerSquareRoot;
+use crate::traits::FlashSwapCallback;
+use sp_runtime::helpers_128bit::multiply_by_rational_with_rounding;
+use sp_runtime::traits::{Bounded, IntegerSquareRoot, Saturating, UniqueSaturatedInto};
+use sp_runtime::{FixedPointNumber, FixedU128, PerThing, Perbill, Permill, Rounding};
 use sp_std::cmp::min;
 
+#[cfg(feature = "std")]
+use serde::{Deserialize, Serialize};
+
+/// Identifier of an asset that can be pooled: either the native currency of `NativeBalance` or
+/// an asset of `Fungibles`. The native currency orders before every asset.
+#[derive(
+	Clone,
+	Copy,
+	Encode,
+	Decode,
+	Eq,
+	PartialEq,
+	Ord,
+	PartialOrd,
+	RuntimeDebug,
+	MaxEncodedLen,
+	TypeInfo,
+)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub enum NativeOrAssetId<AssetId> {
+	/// The native currency.
+	Native,
+	/// An asset of the `Fungibles` pallet.
+	Asset(AssetId),
+}
+
+impl<AssetId> From<AssetId> for NativeOrAssetId<AssetId> {
+	fn from(asset: AssetId) -> Self {
+		NativeOrAssetId::Asset(asset)
+	}
+}
+
 #[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, MaxEncodedLen, TypeInfo)]
 #[scale_info(skip_type_params(T))]
 pub struct AssetPair<T: Config> {
-	pub asset_a: AssetIdOf<T>,
-	pub asset_b: AssetIdOf<T>,
+	pub asset_a: NativeOrAssetIdOf<T>,
+	pub asset_b: NativeOrAssetIdOf<T>,
 }
 
 impl<T: Config> AssetPair<T> {
-	pub fn new(asset_one: AssetIdOf<T>, asset_two: AssetIdOf<T>) -> Self {
+	pub fn new(asset_one: NativeOrAssetIdOf<T>, asset_two: NativeOrAssetIdOf<T>) -> Self {
 		if asset_one <= asset_two {
 			AssetPair { asset_a: asset_one, asset_b: asset_two }
 		} else {
@@ -27,25 +62,65 @@ pub struct LiquidityPool<T: Config> {
 	pub manager: AccountIdOf<T>,
 	pub asset_a_balance: AssetBalanceOf<T>,
 	pub asset_b_balance: AssetBalanceOf<T>,
+	pub fee: Permill,
+	pub k_last: AssetBalanceOf<T>,
+	/// Account that created the pool and whose `deposit` is held until the pool is destroyed.
+	pub depositor: AccountIdOf<T>,
+	pub deposit: BalanceOf<T>,
+	/// Sum of the price of `asset_a` in `asset_b` over every second, like `price0CumulativeLast`
+	/// of Uniswap V2. It wraps around on overflow.
+	pub price_a_cumulative_last: FixedU128,
+	/// Sum of the price of `asset_b` in `asset_a` over every second.
+	pub price_b_cumulative_last: FixedU128,
+	/// Unix time in seconds of the last update of the price accumulators.
+	pub block_timestamp_last: u64,
+}
+
+/// Price accumulators of a pool at a point in time. The average price between two observations
+/// is the difference of their accumulators divided by the seconds between them.
+#[derive(Clone, Copy, Encode, Decode, Eq, PartialEq, RuntimeDebug, MaxEncodedLen, TypeInfo)]
+pub struct PriceObservation {
+	/// Unix time of the observation in seconds
+	pub timestamp: u64,
+	pub price_a_cumulative: FixedU128,
+	pub price_b_cumulative: FixedU128,
 }
 
 impl<T: Config> LiquidityPool<T> {
+	/// Removes `liquidity` from the pool and returns the amounts of both assets sent to `who`,
+	/// which have to be at least the minimum amounts.
 	pub fn remove_liquidity(
 		&mut self,
 		asset_pair: &AssetPair<T>,
 		liquidity: AssetBalanceOf<T>,
+		amount_a_min: AssetBalanceOf<T>,
+		amount_b_min: AssetBalanceOf<T>,
 		who: &AccountIdOf<T>,
-	) -> DispatchResult {
+	) -> Result<(AssetBalanceOf<T>, AssetBalanceOf<T>), DispatchError> {
+		Pallet::<T>::ensure_not_flash_swapping()?;
+		self.update_price_cumulatives(asset_pair);
+		let protocol_fee_on = self.mint_protocol_fee()?;
+
 		// Calculate the amount of assets to return to the liquidity provider
 		let total_issuance = T::Fungibles::total_issuance(self.id.clone());
 		let ratio_a = Self::safe_mul(liquidity, self.asset_a_balance)?;
 		let ratio_b = Self::safe_mul(liquidity, self.asset_b_balance)?;
 		let amount_a = Self::safe_div(ratio_a, total_issuance)?;
 		let amount_b = Self::safe_div(ratio_b, total_issuance)?;
+		ensure!(amount_a >= amount_a_min, Error::<T>::InsufficientAmountA);
+		ensure!(amount_b >= amount_b_min, Error::<T>::InsufficientAmountB);
 
 		// Burn the LP token
 		self.burn_lp(&who, liquidity)?;
 
+		// The last liquidity provider also gets the reserves backing the locked minimum
+		// liquidity, as the pool is destroyed
+		let (amount_a, amount_b) = if self.only_locked_liquidity_left() {
+			(self.asset_a_balance, self.asset_b_balance)
+		} else {
+			(amount_a, amount_b)
+		};
+
 		// Transfer back assets to the liquidity provider
 		self.transfer_out(asset_pair.asset_a, &who, amount_a)?;
 		self.transfer_out(asset_pair.asset_b, &who, amount_b)?;
@@ -53,39 +128,239 @@ impl<T: Config> LiquidityPool<T> {
 		// Update internal balances of the pool
 		self.asset_a_balance = Self::safe_sub(self.asset_a_balance, amount_a)?;
 		self.asset_b_balance = Self::safe_sub(self.asset_b_balance, amount_b)?;
+		if protocol_fee_on {
+			self.k_last = Self::safe_mul(self.asset_a_balance, self.asset_b_balance)?;
+		}
 
-		Ok(())
+		Ok((amount_a, amount_b))
+	}
+
+	/// Whether all LP tokens but the minimum liquidity locked by the first mint were burned.
+	pub fn only_locked_liquidity_left(&self) -> bool {
+		T::Fungibles::total_issuance(self.id.clone()) <= T::MinimumLiquidity::get().into()
 	}
 
 	pub fn swap(
-		&self,
+		&mut self,
 		who: &AccountIdOf<T>,
 		asset_pair: AssetPair<T>,
-		asset_in: AssetIdOf<T>,
-		asset_out: AssetIdOf<T>,
+		asset_in: NativeOrAssetIdOf<T>,
+		asset_out: NativeOrAssetIdOf<T>,
 		amount_in: AssetBalanceOf<T>,
-	) -> DispatchResult {
-		let (token_in_reserve, token_out_reserve) = if asset_out == asset_pair.asset_a {
-			// Rotate the assets in case they want to reverse swap
-			(self.asset_b_balance, self.asset_a_balance)
-		} else {
-			(self.asset_a_balance, self.asset_b_balance)
-		};
+		amount_out_min: AssetBalanceOf<T>,
+	) -> Result<AssetBalanceOf<T>, DispatchError> {
+		let (token_in_reserve, token_out_reserve) = self.reserves_for(&asset_pair, asset_out);
 		ensure!(amount_in > AssetBalanceOf::<T>::zero(), Error::<T>::InsufficientInputAmount);
 		ensure!(
 			token_in_reserve > amount_in && token_out_reserve > AssetBalanceOf::<T>::zero(),
 			Error::<T>::InsufficientLiquidity
 		);
 
-		// Swap the assets
+		// Make sure the pool can deliver at least the minimum accepted output
 		let amount_out =
-			self.calculate_output_for(amount_in, token_in_reserve - amount_in, token_out_reserve)?;
+			self.calculate_output_for(amount_in, token_in_reserve, token_out_reserve)?;
+		ensure!(
+			amount_out > AssetBalanceOf::<T>::zero() && amount_out >= amount_out_min,
+			Error::<T>::InsufficientOutputAmount
+		);
+
+		self.settle_swap(who, &asset_pair, asset_in, asset_out, amount_in, amount_out)?;
+
+		Ok(amount_out)
+	}
+
+	pub fn swap_exact_out(
+		&mut self,
+		who: &AccountIdOf<T>,
+		asset_pair: AssetPair<T>,
+		asset_in: NativeOrAssetIdOf<T>,
+		asset_out: NativeOrAssetIdOf<T>,
+		amount_out: AssetBalanceOf<T>,
+		amount_in_max: AssetBalanceOf<T>,
+	) -> Result<AssetBalanceOf<T>, DispatchError> {
+		let (token_in_reserve, token_out_reserve) = self.reserves_for(&asset_pair, asset_out);
+		ensure!(amount_out > AssetBalanceOf::<T>::zero(), Error::<T>::InsufficientOutputAmount);
+		ensure!(
+			token_in_reserve > AssetBalanceOf::<T>::zero() && token_out_reserve > amount_out,
+			Error::<T>::InsufficientLiquidity
+		);
+
+		// Make sure the required input doesn't exceed the maximum the user is willing to pay
+		let amount_in =
+			self.calculate_input_for(amount_out, token_in_reserve, token_out_reserve)?;
+		ensure!(amount_in <= amount_in_max, Error::<T>::ExcessiveInputAmount);
+
+		self.settle_swap(who, &asset_pair, asset_in, asset_out, amount_in, amount_out)?;
+
+		Ok(amount_in)
+	}
+
+	pub(crate) fn reserves_for(
+		&self,
+		asset_pair: &AssetPair<T>,
+		asset_out: NativeOrAssetIdOf<T>,
+	) -> (AssetBalanceOf<T>, AssetBalanceOf<T>) {
+		if asset_out == asset_pair.asset_a {
+			// Rotate the assets in case they want to reverse swap
+			(self.asset_b_balance, self.asset_a_balance)
+		} else {
+			(self.asset_a_balance, self.asset_b_balance)
+		}
+	}
+
+	fn settle_swap(
+		&mut self,
+		who: &AccountIdOf<T>,
+		asset_pair: &AssetPair<T>,
+		asset_in: NativeOrAssetIdOf<T>,
+		asset_out: NativeOrAssetIdOf<T>,
+		amount_in: AssetBalanceOf<T>,
+		amount_out: AssetBalanceOf<T>,
+	) -> DispatchResult {
+		Pallet::<T>::ensure_not_flash_swapping()?;
+		Pallet::<T>::ensure_trading(asset_pair)?;
+		self.update_price_cumulatives(asset_pair);
+
+		// Swap the assets
 		self.transfer_in(asset_in, &who, amount_in)?;
 		self.transfer_out(asset_out, &who, amount_out)?;
 
+		// Update internal balances of the pool
+		if asset_in == asset_pair.asset_a {
+			self.asset_a_balance = Self::safe_add(self.asset_a_balance, amount_in)?;
+			self.asset_b_balance = Self::safe_sub(self.asset_b_balance, amount_out)?;
+		} else {
+			self.asset_b_balance = Self::safe_add(self.asset_b_balance, amount_in)?;
+			self.asset_a_balance = Self::safe_sub(self.asset_a_balance, amount_out)?;
+		}
+
+		Ok(())
+	}
+
+	/// Sends the output amounts to `who` before being paid for them, like a Uniswap V2 flash
+	/// swap, and returns the amounts paid to the pool. Like in Uniswap V2, the payment is
+	/// whatever the pool account holds above the reserves left after the output when
+	/// `FlashSwapCallback` returns, including assets sent to it before the flash swap.
+	pub fn flash_swap(
+		&mut self,
+		who: &AccountIdOf<T>,
+		asset_pair: &AssetPair<T>,
+		amount_a_out: AssetBalanceOf<T>,
+		amount_b_out: AssetBalanceOf<T>,
+	) -> Result<(AssetBalanceOf<T>, AssetBalanceOf<T>), DispatchError> {
+		let zero_balance = AssetBalanceOf::<T>::zero();
+		ensure!(
+			amount_a_out > zero_balance || amount_b_out > zero_balance,
+			Error::<T>::InsufficientOutputAmount
+		);
+		ensure!(
+			amount_a_out < self.asset_a_balance && amount_b_out < self.asset_b_balance,
+			Error::<T>::InsufficientLiquidity
+		);
+		Pallet::<T>::ensure_not_flash_swapping()?;
+		Pallet::<T>::ensure_trading(asset_pair)?;
+		// The balances of a shared pallet account also belong to other pools
+		Pallet::<T>::ensure_own_pool_account(self)?;
+		self.update_price_cumulatives(asset_pair);
+
+		// Optimistically send the output, the callback has to pay for it
+		if amount_a_out > zero_balance {
+			self.transfer_out(asset_pair.asset_a, who, amount_a_out)?;
+		}
+		if amount_b_out > zero_balance {
+			self.transfer_out(asset_pair.asset_b, who, amount_b_out)?;
+		}
+		FlashSwapLock::<T>::put(true);
+		T::FlashSwapCallback::on_flash_swap(
+			who,
+			&self.manager,
+			asset_pair.asset_a,
+			asset_pair.asset_b,
+			amount_a_out,
+			amount_b_out,
+		)?;
+		FlashSwapLock::<T>::kill();
+
+		let balance_a = self.manager_balance(asset_pair.asset_a);
+		let balance_b = self.manager_balance(asset_pair.asset_b);
+		let amount_a_in = balance_a.saturating_sub(self.asset_a_balance - amount_a_out);
+		let amount_b_in = balance_b.saturating_sub(self.asset_b_balance - amount_b_out);
+		ensure!(
+			amount_a_in > zero_balance || amount_b_in > zero_balance,
+			Error::<T>::InsufficientInputAmount
+		);
+
+		// Like in Uniswap V2, the fee is only charged on the input amounts
+		let (fee_numerator, fee_denominator) = self.fee_ratio();
+		let fee_parts = Self::safe_sub(fee_denominator, fee_numerator)?;
+		let adjusted_a = Self::safe_sub(
+			Self::safe_mul(balance_a, fee_denominator)?,
+			Self::safe_mul(amount_a_in, fee_parts)?,
+		)?;
+		let adjusted_b = Self::safe_sub(
+			Self::safe_mul(balance_b, fee_denominator)?,
+			Self::safe_mul(amount_b_in, fee_parts)?,
+		)?;
+		let k: u128 =
+			Self::safe_mul(self.asset_a_balance, self.asset_b_balance)?.unique_saturated_into();
+		let denominator: u128 =
+			Self::safe_mul(fee_denominator, fee_denominator)?.unique_saturated_into();
+		// A product too large for u128 is certainly above `k`
+		let invariant_holds = multiply_by_rational_with_rounding(
+			adjusted_a.unique_saturated_into(),
+			adjusted_b.unique_saturated_into(),
+			denominator,
+			Rounding::Down,
+		)
+		.map_or(true, |adjusted_k| adjusted_k >= k);
+		ensure!(invariant_holds, Error::<T>::InvariantViolated);
+
+		self.asset_a_balance = balance_a;
+		self.asset_b_balance = balance_b;
+
+		Ok((amount_a_in, amount_b_in))
+	}
+
+	/// Sets the reserves of the pool to the balances of its manager, like `sync` of Uniswap V2,
+	/// so that assets sent straight to the manager become part of the pool.
+	pub fn sync(&mut self, asset_pair: &AssetPair<T>) -> Result<(), DispatchError> {
+		Pallet::<T>::ensure_not_flash_swapping()?;
+		Pallet::<T>::ensure_own_pool_account(self)?;
+		// An empty pool gets its ratio from the first `mint`, donations to it can be skimmed
+		ensure!(
+			!self.asset_a_balance.is_zero() && !self.asset_b_balance.is_zero(),
+			Error::<T>::InsufficientLiquidity
+		);
+		self.update_price_cumulatives(asset_pair);
+
+		self.asset_a_balance = self.manager_balance(asset_pair.asset_a);
+		self.asset_b_balance = self.manager_balance(asset_pair.asset_b);
 		Ok(())
 	}
 
+	/// Sends the balances of the manager above the reserves of the pool to `to`, like `skim` of
+	/// Uniswap V2, and returns the amounts of both assets sent.
+	pub fn skim(
+		&self,
+		asset_pair: &AssetPair<T>,
+		to: &AccountIdOf<T>,
+	) -> Result<(AssetBalanceOf<T>, AssetBalanceOf<T>), DispatchError> {
+		Pallet::<T>::ensure_not_flash_swapping()?;
+		Pallet::<T>::ensure_own_pool_account(self)?;
+
+		let excess_a =
+			self.manager_balance(asset_pair.asset_a).saturating_sub(self.asset_a_balance);
+		let excess_b =
+			self.manager_balance(asset_pair.asset_b).saturating_sub(self.asset_b_balance);
+		if !excess_a.is_zero() {
+			self.transfer_out(asset_pair.asset_a, to, excess_a)?;
+		}
+		if !excess_b.is_zero() {
+			self.transfer_out(asset_pair.asset_b, to, excess_b)?;
+		}
+		Ok((excess_a, excess_b))
+	}
+
 	pub fn calculate_output_for(
 		&self,
 		amount_in: AssetBalanceOf<T>,
@@ -96,16 +371,42 @@ impl<T: Config> LiquidityPool<T> {
 			return Ok(AssetBalanceOf::<T>::zero());
 		}
 
-		// Deduct fixed 0.3% fee from the swap, which is used to reward liquidity providers
-		let amount_without_fee = Self::safe_mul(amount_in, 997u32.into())?;
-		let ratio = Self::safe_mul(amount_without_fee, reserve_out)?;
-		let mut reserve_total = Self::safe_mul(reserve_in, 1000u32.into())?;
+		// Deduct the pool fee from the swap, which is used to reward liquidity providers
+		let (fee_numerator, fee_denominator) = self.fee_ratio();
+		let amount_without_fee = Self::safe_mul(amount_in, fee_numerator)?;
+		let mut reserve_total = Self::safe_mul(reserve_in, fee_denominator)?;
 		reserve_total = Self::safe_add(reserve_total, amount_without_fee)?;
-		let total = Self::safe_div(ratio, reserve_total)?;
+		let total = Self::safe_mul_div(amount_without_fee, reserve_out, reserve_total)?;
+
+		Ok(total)
+	}
+
+	pub fn calculate_input_for(
+		&self,
+		amount_out: AssetBalanceOf<T>,
+		reserve_in: AssetBalanceOf<T>,
+		reserve_out: AssetBalanceOf<T>,
+	) -> Result<AssetBalanceOf<T>, DispatchError> {
+		ensure!(
+			!reserve_in.is_zero() && reserve_out > amount_out,
+			Error::<T>::InsufficientLiquidity
+		);
+
+		// Gross up the output with the pool fee, rounding up in favour of the pool
+		let (fee_numerator, fee_denominator) = self.fee_ratio();
+		let numerator = Self::safe_mul(amount_out, fee_denominator)?;
+		let denominator = Self::safe_mul(Self::safe_sub(reserve_out, amount_out)?, fee_numerator)?;
+		let total =
+			Self::safe_add(Self::safe_mul_div(reserve_in, numerator, denominator)?, One::one())?;
 
 		Ok(total)
 	}
 
+	/// Share of the input amount that is left after the pool fee, as a numerator and denominator
+	fn fee_ratio(&self) -> (AssetBalanceOf<T>, AssetBalanceOf<T>) {
+		(self.fee.left_from_one().deconstruct().into(), Permill::ACCURACY.into())
+	}
+
 	fn calculate_liquidity(
 		&self,
 		total_issuance: AssetBalanceOf<T>,
@@ -143,13 +444,56 @@ impl<T: Config> LiquidityPool<T> {
 		};
 	}
 
+	/// Calculates the amounts to deposit out of the desired ones that match the pool ratio, like
+	/// the Uniswap V2 router. The first deposit into an empty pool sets the ratio, so the desired
+	/// amounts are used as they are.
+	fn optimal_amounts(
+		&self,
+		amount_a_desired: AssetBalanceOf<T>,
+		amount_b_desired: AssetBalanceOf<T>,
+		amount_a_min: AssetBalanceOf<T>,
+		amount_b_min: AssetBalanceOf<T>,
+	) -> Result<(AssetBalanceOf<T>, AssetBalanceOf<T>), DispatchError> {
+		if self.asset_a_balance.is_zero() && self.asset_b_balance.is_zero() {
+			return Ok((amount_a_desired, amount_b_desired));
+		}
+
+		let amount_b_optimal = Self::safe_div(
+			Self::safe_mul(amount_a_desired, self.asset_b_balance)?,
+			self.asset_a_balance,
+		)?;
+		if amount_b_optimal <= amount_b_desired {
+			ensure!(amount_b_optimal >= amount_b_min, Error::<T>::InsufficientAmountB);
+			return Ok((amount_a_desired, amount_b_optimal));
+		}
+
+		// Less than `amount_a_desired` is needed, as `amount_b_desired` is below the optimal one
+		let amount_a_optimal = Self::safe_div(
+			Self::safe_mul(amount_b_desired, self.asset_a_balance)?,
+			self.asset_b_balance,
+		)?;
+		ensure!(amount_a_optimal >= amount_a_min, Error::<T>::InsufficientAmountA);
+		Ok((amount_a_optimal, amount_b_desired))
+	}
+
+	/// Adds liquidity at the pool ratio and returns the amounts of both assets that were taken
+	/// from `who`, which are at most the desired and at least the minimum amounts, and the LP
+	/// tokens minted to `who`.
 	pub fn add_liquidity(
 		&mut self,
 		asset_pair: &AssetPair<T>,
-		amount_a: AssetBalanceOf<T>,
-		amount_b: AssetBalanceOf<T>,
+		amount_a_desired: AssetBalanceOf<T>,
+		amount_b_desired: AssetBalanceOf<T>,
+		amount_a_min: AssetBalanceOf<T>,
+		amount_b_min: AssetBalanceOf<T>,
 		who: &AccountIdOf<T>,
-	) -> DispatchResult {
+	) -> Result<(AssetBalanceOf<T>, AssetBalanceOf<T>, AssetBalanceOf<T>), DispatchError> {
+		let (amount_a, amount_b) =
+			self.optimal_amounts(amount_a_desired, amount_b_desired, amount_a_min, amount_b_min)?;
+		Pallet::<T>::ensure_not_flash_swapping()?;
+		Pallet::<T>::ensure_trading(asset_pair)?;
+		self.update_price_cumulatives(asset_pair);
+		let protocol_fee_on = self.mint_protocol_fee()?;
 		let total_issuance = T::Fungibles::total_issuance(self.id.clone());
 
 		let liquidity = self.calculate_liquidity(
@@ -169,7 +513,103 @@ impl<T: Config> LiquidityPool<T> {
 		// Update the balances in the pool
 		self.asset_a_balance = Self::safe_add(self.asset_a_balance, amount_a)?;
 		self.asset_b_balance = Self::safe_add(self.asset_b_balance, amount_b)?;
+		if protocol_fee_on {
+			self.k_last = Self::safe_mul(self.asset_a_balance, self.asset_b_balance)?;
+		}
 
-		Ok(())
+		Ok((amount_a, amount_b, liquidity))
+	}
+
+	/// Returns the price accumulators as they would be at `now`, adding the current prices for
+	/// the seconds since their last update.
+	pub(crate) fn price_cumulatives_at(&self, now: u64) -> (FixedU128, FixedU128) {
+		let elapsed = now.saturating_sub(self.block_timestamp_last);
+		if elapsed == 0 || self.asset_a_balance.is_zero() || self.asset_b_balance.is_zero() {
+			return (self.price_a_cumulative_last, self.price_b_cumulative_last);
+		}
+
+		let reserve_a: u128 = self.asset_a_balance.unique_saturated_into();
+		let reserve_b: u128 = self.asset_b_balance.unique_saturated_into();
+		// Overflows are fine like in Uniswap V2, as only differences of accumulators are used
+		let accumulate = |cumulative: FixedU128, numerator: u128, denominator: u128| {
+			let price = FixedU128::checked_from_rational(numerator, denominator)
+				.unwrap_or_else(FixedU128::max_value);
+			let growth = price.into_inner().wrapping_mul(elapsed.into());
+			FixedU128::from_inner(cumulative.into_inner().wrapping_add(growth))
+		};
+
+		(
+			accumulate(self.price_a_cumulative_last, reserve_b, reserve_a),
+			accumulate(self.price_b_cumulative_last, reserve_a, reserve_b),
+		)
+	}
+
+	/// Adds the prices before the first change of the reserves in a block to the accumulators,
+	/// so they can't be moved within a single block, and records a price observation at most
+	/// every `ObservationPeriod`.
+	fn update_price_cumulatives(&mut self, asset_pair: &AssetPair<T>) {
+		let now = Pallet::<T>::current_timestamp();
+		if now <= self.block_timestamp_last {
+			return;
+		}
+		(self.price_a_cumulative_last, self.price_b_cumulative_last) =
+			self.price_cumulatives_at(now);
+		self.block_timestamp_last = now;
+
+		let observation = PriceObservation {
+			timestamp: now,
+			price_a_cumulative: self.price_a_cumulative_last,
+			price_b_cumulative: self.price_b_cumulative_last,
+		};
+		PriceObservations::<T>::mutate(asset_pair, |observations| {
+			let due = observations.last().map_or(true, |last| {
+				now.saturating_sub(last.timestamp) >= T::ObservationPeriod::get()
+			});
+			if !due {
+				return;
+			}
+			// Drop the oldest observation to make room for the new one
+			if !observations.is_empty() && observations.len() as u32 >= T::MaxObservations::get() {
+				observations.remove(0);
+			}
+			let _ = observations.try_push(observation);
+		});
+	}
+
+	/// Mints the protocol share of the fee growth since the last liquidity event as LP tokens
+	/// to the protocol fee recipient, using the Uniswap V2 formula. Returns whether the protocol
+	/// fee is on.
+	fn mint_protocol_fee(&mut self) -> Result<bool, DispatchError> {
+		let (recipient, share) = match ProtocolFee::<T>::get() {
+			Some(protocol_fee) => protocol_fee,
+			None => {
+				// Stop tracking the fee growth while the protocol fee is off
+				self.k_last = AssetBalanceOf::<T>::zero();
+				return Ok(false);
+			},
+		};
+		if self.k_last.is_zero() {
+			return Ok(true);
+		}
+
+		let root_k = Self::safe_mul(self.asset_a_balance, self.asset_b_balance)?.integer_sqrt();
+		let root_k_last = self.k_last.integer_sqrt();
+		if root_k > root_k_last {
+			// The protocol gets `share` of the growth in sqrt(k), which is the fee growth:
+			// liquidity = supply * share * (√k - √kLast) / ((1 - share) * √k + share * √kLast)
+			let total_issuance = T::Fungibles::total_issuance(self.id.clone());
+			let growth = Self::safe_mul(total_issuance, Self::safe_sub(root_k, root_k_last)?)?;
+			let numerator = share.mul_floor(growth);
+			let denominator = Self::safe_add(
+				share.left_from_one().mul_floor(root_k),
+				share.mul_floor(root_k_last),
+			)?;
+			let liquidity = Self::safe_div(numerator, denominator)?;
+			if liquidity > AssetBalanceOf::<T>::zero() {
+				T::Fungibles::mint_into(self.id.clone(), &recipient, liquidity)?;
+			}
+		}
+
+		Ok(true)
 	}
 }
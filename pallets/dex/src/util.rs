@@ -1,16 +1,128 @@
+use crate::types::AssetPair;
 use crate::*;
 use frame_support::ensure;
+use frame_support::traits::fungibles::metadata::Inspect as MetadataInspect;
 use frame_support::traits::tokens::{Fortitude, Precision, Preservation};
-use sp_runtime::traits::{CheckedAdd, CheckedDiv, CheckedMul, CheckedSub};
-use sp_runtime::DispatchError;
+use frame_support::traits::UnixTime;
+use frame_system::pallet_prelude::BlockNumberFor;
+use sp_runtime::helpers_128bit::multiply_by_rational_with_rounding;
+use sp_runtime::traits::{
+	AccountIdConversion, CheckedAdd, CheckedDiv, CheckedMul, CheckedSub, Saturating,
+	UniqueSaturatedInto, Zero,
+};
+use sp_runtime::{DispatchError, Rounding};
 
 impl<T: Config> Pallet<T> {
 	pub(super) fn ensure_assets_exist(
-		asset_a: AssetIdOf<T>,
-		asset_b: AssetIdOf<T>,
+		asset_a: NativeOrAssetIdOf<T>,
+		asset_b: NativeOrAssetIdOf<T>,
 	) -> Result<(), DispatchError> {
-		ensure!(T::Fungibles::asset_exists(asset_a), Error::<T>::UnknownAssetId);
-		ensure!(T::Fungibles::asset_exists(asset_b), Error::<T>::UnknownAssetId);
+		ensure!(NativeOrAssetAdapter::<T>::asset_exists(asset_a), Error::<T>::UnknownAssetId);
+		ensure!(NativeOrAssetAdapter::<T>::asset_exists(asset_b), Error::<T>::UnknownAssetId);
+		Ok(())
+	}
+
+	/// Returns the number of decimals of `asset`, from the metadata of the assets or
+	/// `TokenDecimals` for the native currency.
+	pub(super) fn asset_decimals(asset: NativeOrAssetIdOf<T>) -> u32 {
+		match asset {
+			NativeOrAssetId::Native => T::TokenDecimals::get(),
+			NativeOrAssetId::Asset(id) => T::Fungibles::decimals(id).into(),
+		}
+	}
+
+	/// Allocates the asset id of the LP token for a new pool from the range reserved for LP
+	/// tokens, skipping ids that were already taken by other assets. At most
+	/// `MaxLpAssetIdProbes` ids are tried.
+	pub(super) fn next_lp_asset_id() -> Result<AssetIdOf<T>, DispatchError> {
+		let max_lp_asset_id = T::MaxLpAssetId::get();
+		let mut lp_asset_id = NextLpAssetId::<T>::get().unwrap_or_else(T::MinLpAssetId::get);
+		let mut probes = 1;
+		while T::Fungibles::asset_exists(lp_asset_id) {
+			ensure!(
+				lp_asset_id < max_lp_asset_id && probes < T::MaxLpAssetIdProbes::get(),
+				Error::<T>::AssetLimitReached
+			);
+			lp_asset_id += 1;
+			probes += 1;
+		}
+		ensure!(lp_asset_id <= max_lp_asset_id, Error::<T>::AssetLimitReached);
+
+		NextLpAssetId::<T>::put(lp_asset_id.saturating_add(1));
+		Ok(lp_asset_id)
+	}
+
+	/// Moves `NextLpAssetId` past the ids taken by other assets with at most `limit` of weight, so
+	/// that `create_pool` doesn't have to probe them again. A `create_pool` that runs out of
+	/// probes fails and can't store how far it got, so this lets it succeed in a later block.
+	pub(super) fn skip_taken_lp_asset_ids(limit: Weight) -> Weight {
+		let db_weight = T::DbWeight::get();
+		// `NextLpAssetId`, at least one asset and the write of the new id
+		if db_weight.reads_writes(2, 1).any_gt(limit) {
+			return Weight::zero()
+		}
+
+		let start = NextLpAssetId::<T>::get().unwrap_or_else(T::MinLpAssetId::get);
+		let max_lp_asset_id = T::MaxLpAssetId::get();
+		let read = db_weight.reads(1);
+		let mut used = db_weight.reads_writes(1, 1);
+		let mut lp_asset_id = start;
+		while lp_asset_id < max_lp_asset_id && !used.saturating_add(read).any_gt(limit) {
+			used = used.saturating_add(read);
+			if !T::Fungibles::asset_exists(lp_asset_id) {
+				break
+			}
+			lp_asset_id += 1;
+		}
+
+		if lp_asset_id == start {
+			return used.saturating_sub(db_weight.writes(1))
+		}
+		NextLpAssetId::<T>::put(lp_asset_id);
+		used
+	}
+
+	/// Returns the account holding the assets of the pool whose LP token is `pool_id`.
+	pub fn pool_account(pool_id: AssetIdOf<T>) -> AccountIdOf<T> {
+		T::PalletId::get().into_sub_account_truncating(pool_id)
+	}
+
+	/// Ensures that `pool` holds its assets in its own account, rather than in the pallet
+	/// account that the pools created before per pool accounts share.
+	pub(super) fn ensure_own_pool_account(pool: &LiquidityPool<T>) -> Result<(), DispatchError> {
+		ensure!(pool.manager == Self::pool_account(pool.id), Error::<T>::SharedPoolAccount);
+		Ok(())
+	}
+
+	/// Current unix time in seconds, as used by the price accumulators of the pools. It is zero
+	/// at genesis, where the time isn't set yet and reading it logs an error.
+	pub(super) fn current_timestamp() -> u64 {
+		if frame_system::Pallet::<T>::block_number().is_zero() {
+			return 0
+		}
+		T::Time::now().as_secs()
+	}
+
+	pub(super) fn ensure_not_flash_swapping() -> Result<(), DispatchError> {
+		ensure!(!FlashSwapLock::<T>::get(), Error::<T>::FlashSwapInProgress);
+		Ok(())
+	}
+
+	/// Ensures that the pool of `asset_pair` can be traded in and added to, which takes trading
+	/// to be enabled and the pool not to be paused.
+	pub(super) fn ensure_trading(asset_pair: &AssetPair<T>) -> Result<(), DispatchError> {
+		ensure!(TradingEnabled::<T>::get(), Error::<T>::TradingDisabled);
+		ensure!(!PausedPools::<T>::get(asset_pair), Error::<T>::PoolPaused);
+		Ok(())
+	}
+
+	pub(super) fn ensure_not_expired(
+		deadline: Option<BlockNumberFor<T>>,
+	) -> Result<(), DispatchError> {
+		if let Some(deadline) = deadline {
+			let now = frame_system::Pallet::<T>::block_number();
+			ensure!(now <= deadline, Error::<T>::Expired);
+		}
 		Ok(())
 	}
 }
@@ -52,22 +164,63 @@ impl<T: Config> LiquidityPool<T> {
 		Self::checked_operation(&x, &y, |a, b| AssetBalanceOf::<T>::checked_sub(a, b))
 	}
 
+	/// Returns `x * y / z` rounded down, with the product taken in 256 bits so that it only fails
+	/// when the result itself doesn't fit.
+	pub(super) fn safe_mul_div(
+		x: AssetBalanceOf<T>,
+		y: AssetBalanceOf<T>,
+		z: AssetBalanceOf<T>,
+	) -> Result<AssetBalanceOf<T>, DispatchError> {
+		let result = multiply_by_rational_with_rounding(
+			x.unique_saturated_into(),
+			y.unique_saturated_into(),
+			z.unique_saturated_into(),
+			Rounding::Down,
+		)
+		.ok_or(Error::<T>::Arithmetic)?;
+		AssetBalanceOf::<T>::try_from(result).map_err(|_| Error::<T>::Arithmetic.into())
+	}
+
+	/// Returns the balance of `asset` held by the manager, leaving out the existential deposit
+	/// that the creator of the pool paid in the native currency.
+	pub(super) fn manager_balance(&self, asset: NativeOrAssetIdOf<T>) -> AssetBalanceOf<T> {
+		let balance = NativeOrAssetAdapter::<T>::balance(asset, &self.manager);
+		match asset {
+			NativeOrAssetId::Native => balance.saturating_sub(
+				NativeOrAssetAdapter::<T>::minimum_balance(NativeOrAssetId::Native),
+			),
+			NativeOrAssetId::Asset(_) => balance,
+		}
+	}
+
 	pub(super) fn transfer_in(
 		&self,
-		asset: AssetIdOf<T>,
+		asset: NativeOrAssetIdOf<T>,
 		from: &AccountIdOf<T>,
 		amount: AssetBalanceOf<T>,
 	) -> Result<AssetBalanceOf<T>, DispatchError> {
-		T::Fungibles::transfer(asset, from, &self.manager, amount, Preservation::Expendable)
+		NativeOrAssetAdapter::<T>::transfer(
+			asset,
+			from,
+			&self.manager,
+			amount,
+			Preservation::Expendable,
+		)
 	}
 
 	pub(super) fn transfer_out(
 		&self,
-		asset: AssetIdOf<T>,
+		asset: NativeOrAssetIdOf<T>,
 		to: &AccountIdOf<T>,
 		amount: AssetBalanceOf<T>,
 	) -> Result<AssetBalanceOf<T>, DispatchError> {
-		T::Fungibles::transfer(asset, &self.manager, to, amount, Preservation::Expendable)
+		NativeOrAssetAdapter::<T>::transfer(
+			asset,
+			&self.manager,
+			to,
+			amount,
+			Preservation::Expendable,
+		)
 	}
 
 	pub(super) fn burn_lp(
@@ -0,0 +1,222 @@
+//! Weights for pallet_dex
+//!
+//! THESE WEIGHTS ARE HAND-WRITTEN PLACEHOLDERS, NOT THE OUTPUT OF A BENCHMARK RUN.
+//! Their execution times and proof sizes are estimated from the storage each call accesses, so
+//! they come without the storage annotations of generated weights. They have to be replaced
+//! before the pallet goes to production, by regenerating this file on reference hardware with:
+
+// ./target/release/node-template
+// benchmark
+// pallet
+// --chain
+// dev
+// --pallet
+// pallet_dex
+// --extrinsic
+// *
+// --steps=50
+// --repeat=20
+// --execution=wasm
+// --wasm-execution=compiled
+// --output
+// pallets/dex/src/weights.rs
+// --template
+// ../../.maintain/frame-weight-template.hbs
+
+#![cfg_attr(rustfmt, rustfmt_skip)]
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+
+use frame_support::{traits::Get, weights::{Weight, constants::RocksDbWeight}};
+use core::marker::PhantomData;
+
+/// Weight functions needed for pallet_dex.
+pub trait WeightInfo {
+	fn create_pool() -> Weight;
+	fn mint() -> Weight;
+	fn burn_partial() -> Weight;
+	fn burn_full() -> Weight;
+	fn swap() -> Weight;
+	fn swap_exact_tokens_for_tokens() -> Weight;
+	fn swap_tokens_for_exact_tokens() -> Weight;
+	fn swap_along_path(n: u32, ) -> Weight;
+	fn set_protocol_fee() -> Weight;
+	fn flash_swap() -> Weight;
+	fn sync() -> Weight;
+	fn skim() -> Weight;
+	fn pause_pool() -> Weight;
+	fn unpause_pool() -> Weight;
+	fn set_trading_enabled() -> Weight;
+	fn destroy_pool() -> Weight;
+}
+
+/// Placeholder weights for pallet_dex until they are benchmarked, see the top of this file.
+pub struct SubstrateWeight<T>(PhantomData<T>);
+impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
+	fn create_pool() -> Weight {
+		Weight::from_parts(76_204_000, 15611)
+			.saturating_add(T::DbWeight::get().reads(9_u64))
+			.saturating_add(T::DbWeight::get().writes(6_u64))
+	}
+	fn mint() -> Weight {
+		Weight::from_parts(141_380_000, 21658)
+			.saturating_add(T::DbWeight::get().reads(13_u64))
+			.saturating_add(T::DbWeight::get().writes(11_u64))
+	}
+	fn burn_partial() -> Weight {
+		Weight::from_parts(139_012_000, 21658)
+			.saturating_add(T::DbWeight::get().reads(13_u64))
+			.saturating_add(T::DbWeight::get().writes(11_u64))
+	}
+	fn burn_full() -> Weight {
+		Weight::from_parts(134_176_000, 22473)
+			.saturating_add(T::DbWeight::get().reads(13_u64))
+			.saturating_add(T::DbWeight::get().writes(11_u64))
+	}
+	fn swap() -> Weight {
+		Weight::from_parts(100_915_000, 16440)
+			.saturating_add(T::DbWeight::get().reads(9_u64))
+			.saturating_add(T::DbWeight::get().writes(8_u64))
+	}
+	fn swap_exact_tokens_for_tokens() -> Weight {
+		Weight::from_parts(101_362_000, 16440)
+			.saturating_add(T::DbWeight::get().reads(9_u64))
+			.saturating_add(T::DbWeight::get().writes(8_u64))
+	}
+	fn swap_tokens_for_exact_tokens() -> Weight {
+		Weight::from_parts(102_645_000, 16440)
+			.saturating_add(T::DbWeight::get().reads(9_u64))
+			.saturating_add(T::DbWeight::get().writes(8_u64))
+	}
+	fn swap_along_path(n: u32, ) -> Weight {
+		Weight::from_parts(2_861_000, 2389)
+			.saturating_add(Weight::from_parts(96_435_000, 0).saturating_mul(n.into()))
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().reads((6_u64).saturating_mul(n.into())))
+			.saturating_add(T::DbWeight::get().writes((6_u64).saturating_mul(n.into())))
+			.saturating_add(Weight::from_parts(0, 12344).saturating_mul(n.into()))
+	}
+	fn set_protocol_fee() -> Weight {
+		Weight::from_parts(9_847_000, 0)
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	fn flash_swap() -> Weight {
+		Weight::from_parts(104_512_000, 16936)
+			.saturating_add(T::DbWeight::get().reads(10_u64))
+			.saturating_add(T::DbWeight::get().writes(9_u64))
+	}
+	fn sync() -> Weight {
+		Weight::from_parts(42_861_000, 11648)
+			.saturating_add(T::DbWeight::get().reads(8_u64))
+			.saturating_add(T::DbWeight::get().writes(2_u64))
+	}
+	fn skim() -> Weight {
+		Weight::from_parts(68_472_000, 11746)
+			.saturating_add(T::DbWeight::get().reads(8_u64))
+			.saturating_add(T::DbWeight::get().writes(6_u64))
+	}
+	fn pause_pool() -> Weight {
+		Weight::from_parts(24_318_000, 6360)
+			.saturating_add(T::DbWeight::get().reads(4_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	fn unpause_pool() -> Weight {
+		Weight::from_parts(24_705_000, 6360)
+			.saturating_add(T::DbWeight::get().reads(4_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	fn set_trading_enabled() -> Weight {
+		Weight::from_parts(9_262_000, 0)
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	fn destroy_pool() -> Weight {
+		Weight::from_parts(42_516_000, 7414)
+			.saturating_add(T::DbWeight::get().reads(6_u64))
+			.saturating_add(T::DbWeight::get().writes(5_u64))
+	}
+}
+
+// For backwards compatibility and tests
+impl WeightInfo for () {
+	fn create_pool() -> Weight {
+		Weight::from_parts(76_204_000, 15611)
+			.saturating_add(RocksDbWeight::get().reads(9_u64))
+			.saturating_add(RocksDbWeight::get().writes(6_u64))
+	}
+	fn mint() -> Weight {
+		Weight::from_parts(141_380_000, 21658)
+			.saturating_add(RocksDbWeight::get().reads(13_u64))
+			.saturating_add(RocksDbWeight::get().writes(11_u64))
+	}
+	fn burn_partial() -> Weight {
+		Weight::from_parts(139_012_000, 21658)
+			.saturating_add(RocksDbWeight::get().reads(13_u64))
+			.saturating_add(RocksDbWeight::get().writes(11_u64))
+	}
+	fn burn_full() -> Weight {
+		Weight::from_parts(134_176_000, 22473)
+			.saturating_add(RocksDbWeight::get().reads(13_u64))
+			.saturating_add(RocksDbWeight::get().writes(11_u64))
+	}
+	fn swap() -> Weight {
+		Weight::from_parts(100_915_000, 16440)
+			.saturating_add(RocksDbWeight::get().reads(9_u64))
+			.saturating_add(RocksDbWeight::get().writes(8_u64))
+	}
+	fn swap_exact_tokens_for_tokens() -> Weight {
+		Weight::from_parts(101_362_000, 16440)
+			.saturating_add(RocksDbWeight::get().reads(9_u64))
+			.saturating_add(RocksDbWeight::get().writes(8_u64))
+	}
+	fn swap_tokens_for_exact_tokens() -> Weight {
+		Weight::from_parts(102_645_000, 16440)
+			.saturating_add(RocksDbWeight::get().reads(9_u64))
+			.saturating_add(RocksDbWeight::get().writes(8_u64))
+	}
+	fn swap_along_path(n: u32, ) -> Weight {
+		Weight::from_parts(2_861_000, 2389)
+			.saturating_add(Weight::from_parts(96_435_000, 0).saturating_mul(n.into()))
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().reads((6_u64).saturating_mul(n.into())))
+			.saturating_add(RocksDbWeight::get().writes((6_u64).saturating_mul(n.into())))
+			.saturating_add(Weight::from_parts(0, 12344).saturating_mul(n.into()))
+	}
+	fn set_protocol_fee() -> Weight {
+		Weight::from_parts(9_847_000, 0)
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	fn flash_swap() -> Weight {
+		Weight::from_parts(104_512_000, 16936)
+			.saturating_add(RocksDbWeight::get().reads(10_u64))
+			.saturating_add(RocksDbWeight::get().writes(9_u64))
+	}
+	fn sync() -> Weight {
+		Weight::from_parts(42_861_000, 11648)
+			.saturating_add(RocksDbWeight::get().reads(8_u64))
+			.saturating_add(RocksDbWeight::get().writes(2_u64))
+	}
+	fn skim() -> Weight {
+		Weight::from_parts(68_472_000, 11746)
+			.saturating_add(RocksDbWeight::get().reads(8_u64))
+			.saturating_add(RocksDbWeight::get().writes(6_u64))
+	}
+	fn pause_pool() -> Weight {
+		Weight::from_parts(24_318_000, 6360)
+			.saturating_add(RocksDbWeight::get().reads(4_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	fn unpause_pool() -> Weight {
+		Weight::from_parts(24_705_000, 6360)
+			.saturating_add(RocksDbWeight::get().reads(4_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	fn set_trading_enabled() -> Weight {
+		Weight::from_parts(9_262_000, 0)
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	fn destroy_pool() -> Weight {
+		Weight::from_parts(42_516_000, 7414)
+			.saturating_add(RocksDbWeight::get().reads(6_u64))
+			.saturating_add(RocksDbWeight::get().writes(5_u64))
+	}
+}
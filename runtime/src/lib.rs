@@ -10,6 +10,8 @@ use codec::{Decode, Encode};
 use frame_support::traits::AsEnsureOriginWithArg;
 use frame_support::PalletId;
 use frame_system::{EnsureRoot, EnsureSigned};
+use pallet_dex::traits::OraclePrice;
+use pallet_dex_runtime_api::PoolInfo;
 use pallet_grandpa::AuthorityId as GrandpaId;
 use sp_api::impl_runtime_apis;
 use sp_consensus_aura::sr25519::AuthorityId as AuraId;
@@ -64,6 +66,12 @@ pub type AccountId = <<Signature as Verify>::Signer as IdentifyAccount>::Account
 /// Balance of an account.
 pub type Balance = u128;
 
+/// Identifier of an asset in the assets pallet.
+pub type AssetId = u32;
+
+/// Identifier of an asset that can be pooled on the dex, including the native currency.
+pub type DexAssetId = pallet_dex::NativeOrAssetId<AssetId>;
+
 /// Index of a transaction in the chain.
 pub type Nonce = u32;
 
@@ -105,11 +113,12 @@ pub const VERSION: RuntimeVersion = RuntimeVersion {
 	//   runtime in substitute for the on-chain Wasm runtime unless all of `spec_name`,
 	//   `spec_version`, and `authoring_version` are the same between Wasm and native.
 	// This value is set to 100 to notify Polkadot-JS App (https://polkadot.js.org/apps) to use
-	//   the compatible custom types.
-	spec_version: 100,
+	//   the compatible custom types. Bumped to run the MeMeSwap storage migrations.
+	spec_version: 101,
 	impl_version: 1,
 	apis: RUNTIME_API_VERSIONS,
-	transaction_version: 1,
+	// Bumped because the MeMeSwap `mint`, `burn` and `swap` calls changed their arguments.
+	transaction_version: 2,
 	state_version: 1,
 };
 
@@ -153,6 +162,22 @@ parameter_types! {
 	pub const MemeSwapPallet: PalletId = PalletId(*b"MeMeSwap");
 	pub const TokenDecimals: u32 = 10;
 	pub const MinimumLiquidity: u32 = 1000;
+	pub const MaxHops: u32 = 4;
+	/// Pools searched for routes by other pallets, the `DexApi` runtime API searches them all.
+	pub const MaxPoolsScanned: u32 = 32;
+	/// LP tokens get asset ids from the top half of the id space, out of the way of user assets.
+	pub const MinLpAssetId: AssetId = 1 << 31;
+	pub const MaxLpAssetId: AssetId = AssetId::MAX;
+	/// Asset ids taken by other assets that a new LP token can skip within one `create_pool`.
+	pub const MaxLpAssetIdProbes: u32 = 8;
+	/// Swap fees of 0.05%, 0.3% and 1% that pool creators can choose from.
+	pub SwapFeeTiers: Vec<Permill> =
+		vec![Permill::from_parts(500), Permill::from_parts(3_000), Permill::from_parts(10_000)];
+	/// One unit of the native currency is held from pool creators until the pool is destroyed.
+	pub const PoolDeposit: Balance = 10u128.pow(10);
+	/// Price observations of the pools every 30 minutes over the last day.
+	pub const MaxObservations: u32 = 48;
+	pub const ObservationPeriod: u64 = 30 * 60;
 }
 
 // Configure FRAME pallets to include in runtime.
@@ -250,7 +275,7 @@ impl pallet_balances::Config for Runtime {
 	type WeightInfo = pallet_balances::weights::SubstrateWeight<Runtime>;
 	type FreezeIdentifier = ();
 	type MaxFreezes = ConstU32<50>;
-	type RuntimeHoldReason = ();
+	type RuntimeHoldReason = RuntimeHoldReason;
 	type MaxHolds = ConstU32<50>;
 }
 
@@ -284,10 +309,12 @@ parameter_types! {
 impl pallet_assets::Config for Runtime {
 	type RuntimeEvent = RuntimeEvent;
 	type Balance = u128;
-	type AssetId = u32;
-	type AssetIdParameter = codec::Compact<u32>;
+	type AssetId = AssetId;
+	type AssetIdParameter = codec::Compact<AssetId>;
 	type Currency = Balances;
-	type CreateOrigin = AsEnsureOriginWithArg<EnsureSigned<AccountId>>;
+	// The asset ids from `MinLpAssetId` up are left to the LP tokens of the dex
+	type CreateOrigin =
+		pallet_dex::EnsureNotLpAssetId<Runtime, AsEnsureOriginWithArg<EnsureSigned<AccountId>>>;
 	type ForceOrigin = EnsureRoot<AccountId>;
 	type AssetDeposit = AssetDeposit;
 	type AssetAccountDeposit = ConstU128<1>;
@@ -318,11 +345,25 @@ impl Convert<AuraId, AccountId> for AuthorityToAccount {
 /// Configure the pallet-dex in pallets/dex.
 impl pallet_dex::Config for Runtime {
 	type RuntimeEvent = RuntimeEvent;
+	type RuntimeHoldReason = RuntimeHoldReason;
 	type NativeBalance = Balances;
 	type Fungibles = Assets;
 	type PalletId = MemeSwapPallet;
 	type TokenDecimals = TokenDecimals;
 	type MinimumLiquidity = MinimumLiquidity;
+	type MaxHops = MaxHops;
+	type MaxPoolsScanned = MaxPoolsScanned;
+	type MinLpAssetId = MinLpAssetId;
+	type MaxLpAssetId = MaxLpAssetId;
+	type MaxLpAssetIdProbes = MaxLpAssetIdProbes;
+	type SwapFeeTiers = SwapFeeTiers;
+	type PoolDeposit = PoolDeposit;
+	type Time = Timestamp;
+	type MaxObservations = MaxObservations;
+	type ObservationPeriod = ObservationPeriod;
+	type FlashSwapCallback = ();
+	type AdminOrigin = EnsureRoot<AccountId>;
+	type WeightInfo = pallet_dex::weights::SubstrateWeight<Runtime>;
 }
 
 // Create the runtime by composing the FRAME pallets that were previously configured.
@@ -363,6 +404,12 @@ pub type UncheckedExtrinsic =
 	generic::UncheckedExtrinsic<Address, RuntimeCall, Signature, SignedExtra>;
 /// The payload being signed in transactions.
 pub type SignedPayload = generic::SignedPayload<RuntimeCall, SignedExtra>;
+/// Migrations to apply on runtime upgrade.
+pub type Migrations = (
+	pallet_dex::migrations::v1::MigrateToV1<Runtime>,
+	pallet_dex::migrations::v2::MigrateToV2<Runtime>,
+);
+
 /// Executive: handles dispatch to the various modules.
 pub type Executive = frame_executive::Executive<
 	Runtime,
@@ -370,6 +417,7 @@ pub type Executive = frame_executive::Executive<
 	frame_system::ChainContext<Runtime>,
 	Runtime,
 	AllPalletsWithSystem,
+	Migrations,
 >;
 
 #[cfg(feature = "runtime-benchmarks")]
@@ -512,6 +560,53 @@ impl_runtime_apis! {
 		}
 	}
 
+	impl pallet_dex_runtime_api::DexApi<Block, DexAssetId, AssetId, Balance> for Runtime {
+		fn quote_exact_in(asset_in: DexAssetId, asset_out: DexAssetId, amount_in: Balance) -> Option<Balance> {
+			Dex::get_price_for(asset_in, amount_in, asset_out).ok()
+		}
+
+		fn quote_exact_out(asset_in: DexAssetId, asset_out: DexAssetId, amount_out: Balance) -> Option<Balance> {
+			Dex::get_input_for(asset_in, asset_out, amount_out).ok()
+		}
+
+		fn get_reserves(asset_a: DexAssetId, asset_b: DexAssetId) -> Option<(Balance, Balance)> {
+			Dex::get_reserves(asset_a, asset_b).ok()
+		}
+
+		fn get_pool(asset_a: DexAssetId, asset_b: DexAssetId) -> Option<PoolInfo<DexAssetId, AssetId, Balance>> {
+			Dex::get_pool(asset_a, asset_b).map(|(pool_key, pool)| PoolInfo {
+				lp_asset_id: pool.id,
+				asset_a: pool_key.asset_a,
+				asset_b: pool_key.asset_b,
+				reserve_a: pool.asset_a_balance,
+				reserve_b: pool.asset_b_balance,
+				fee: pool.fee,
+			})
+		}
+
+		fn list_pools() -> Vec<PoolInfo<DexAssetId, AssetId, Balance>> {
+			pallet_dex::LiquidityPools::<Runtime>::iter()
+				.map(|(pool_key, pool)| PoolInfo {
+					lp_asset_id: pool.id,
+					asset_a: pool_key.asset_a,
+					asset_b: pool_key.asset_b,
+					reserve_a: pool.asset_a_balance,
+					reserve_b: pool.asset_b_balance,
+					fee: pool.fee,
+				})
+				.collect()
+		}
+
+		fn find_best_route(
+			asset_in: DexAssetId,
+			amount_in: Balance,
+			asset_out: DexAssetId,
+			max_hops: u32,
+		) -> Option<(Vec<DexAssetId>, Balance)> {
+			Dex::find_best_route(asset_in, amount_in, asset_out, max_hops).ok()
+		}
+	}
+
 	impl pallet_transaction_payment_rpc_runtime_api::TransactionPaymentApi<Block, Balance> for Runtime {
 		fn query_info(
 			uxt: <Block as BlockT>::Extrinsic,